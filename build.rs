@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Year of the day modules directly in src, must match ROOT_YEAR in lib.rs.
+// Days of other years live in their own module folder, such as src/y2023/dayNN.rs.
+const ROOT_YEAR: u32 = 2024;
+
+fn main() {
+    let src = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+
+    let mut years = BTreeMap::new();
+    years.insert(ROOT_YEAR, day_modules(&src));
+    for e in fs::read_dir(&src).unwrap() {
+        let e = e.unwrap();
+        let name = e.file_name();
+        let year = name
+            .to_str()
+            .and_then(|s| s.strip_prefix('y')?.parse().ok());
+        if let (Some(year), true) = (year, e.path().is_dir()) {
+            years.insert(year, day_modules(&e.path()));
+        }
+    }
+
+    let mut out = String::from("// Generated by build.rs from the day modules found in src.\n\n");
+    for (year, days) in &years {
+        for (i, &(day, _)) in days.iter().enumerate() {
+            if day != i + 1 {
+                panic!(
+                    "day {} of {} is missing, days must be contiguous",
+                    i + 1,
+                    year
+                );
+            }
+        }
+        let prefix = if *year == ROOT_YEAR {
+            for (day, path) in days {
+                writeln!(
+                    out,
+                    "#[path = {:?}]\nmod day{:02};",
+                    path.to_str().unwrap(),
+                    day
+                )
+                .unwrap();
+            }
+            String::new()
+        } else {
+            writeln!(out, "mod y{} {{", year).unwrap();
+            for (day, path) in days {
+                let path = path.to_str().unwrap();
+                writeln!(out, "    #[path = {:?}]\n    pub mod day{:02};", path, day).unwrap();
+            }
+            writeln!(out, "}}").unwrap();
+            format!("y{}::", year)
+        };
+        let fns: Vec<_> = days
+            .iter()
+            .map(|(day, _)| format!("solution::solve::<{}day{:02}::Day>", prefix, day))
+            .collect();
+        writeln!(
+            out,
+            "\nstatic DAY_FNS_{}: &[DayFunc] = &[{}];\n",
+            year,
+            fns.join(", ")
+        )
+        .unwrap();
+    }
+    let v: Vec<_> = years
+        .keys()
+        .map(|y| format!("({y}, DAY_FNS_{y})"))
+        .collect();
+    writeln!(
+        out,
+        "static YEARS: &[(u32, &[DayFunc])] = &[{}];",
+        v.join(", ")
+    )
+    .unwrap();
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(dest, out).unwrap();
+
+    git_rev(src.parent().unwrap());
+}
+
+// Records the revision being built in GIT_REV, for the run history.
+fn git_rev(root: &Path) {
+    let out = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .current_dir(root)
+        .output();
+    let Ok(out) = out else {
+        return;
+    };
+    let rev = String::from_utf8_lossy(&out.stdout).trim().to_owned();
+    if out.status.success() && !rev.is_empty() {
+        println!("cargo:rustc-env=GIT_REV={}", rev);
+    }
+    // Commits, checkouts and staging change these, the source files are watched above.
+    // A commit moves the branch HEAD points to, which is either a file of its own
+    // or a line in packed-refs.
+    let git = root.join(".git");
+    let mut watch = vec![git.join("HEAD"), git.join("index"), git.join("packed-refs")];
+    if let Ok(head) = fs::read_to_string(git.join("HEAD")) {
+        if let Some(r) = head.trim().strip_prefix("ref: ") {
+            watch.push(git.join(r));
+        }
+    }
+    for path in watch.iter().filter(|p| p.exists()) {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+// Day modules in dir, sorted by day.
+fn day_modules(dir: &Path) -> Vec<(usize, PathBuf)> {
+    let mut v: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|e| {
+            let path = e.ok()?.path();
+            let name = path.file_name()?.to_str()?;
+            let day = name
+                .strip_prefix("day")?
+                .strip_suffix(".rs")?
+                .parse()
+                .ok()?;
+            Some((day, path))
+        })
+        .collect();
+    v.sort();
+    v
+}
@@ -0,0 +1,136 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+// Whether allocations are counted, with the alloc-stats feature.
+pub const ENABLED: bool = cfg!(feature = "alloc-stats");
+
+// Heap allocations made by a thread while it was measured.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    // Number of allocations, reallocations included.
+    pub count: u64,
+    // Bytes requested by those allocations.
+    pub bytes: u64,
+    // Highest amount of heap in use above the level at the start.
+    pub peak: u64,
+}
+
+#[derive(Clone, Copy)]
+struct Counters {
+    count: u64,
+    bytes: u64,
+    live: i64,
+    peak: i64,
+}
+
+const ZERO: Counters = Counters {
+    count: 0,
+    bytes: 0,
+    live: 0,
+    peak: 0,
+};
+
+// Counters are per thread, so that days running in parallel don't mix.
+// Memory freed by another thread than the one allocating it is charged
+// to the freeing one.
+thread_local! {
+    static COUNTERS: Cell<Counters> = const { Cell::new(ZERO) };
+}
+
+fn record(alloc: usize, free: usize) {
+    // The counters are const and have no destructor, so they are always there,
+    // even while the thread exits.
+    let _ = COUNTERS.try_with(|c| {
+        let mut x = c.get();
+        if alloc > 0 {
+            x.count += 1;
+            x.bytes += alloc as u64;
+        }
+        x.live += alloc as i64 - free as i64;
+        x.peak = x.peak.max(x.live);
+        c.set(x);
+    });
+}
+
+// Global allocator that counts the allocations of each thread
+// and leaves the work to the system allocator.
+pub struct Counting;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc(layout);
+        if !p.is_null() {
+            record(layout.size(), 0);
+        }
+        p
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc_zeroed(layout);
+        if !p.is_null() {
+            record(layout.size(), 0);
+        }
+        p
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let p = System.realloc(ptr, layout, new_size);
+        if !p.is_null() {
+            record(new_size, layout.size());
+        }
+        p
+    }
+}
+
+// Runs f, and returns the allocations it made on this thread
+// if they are counted.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    if !ENABLED {
+        return (f(), None);
+    }
+    COUNTERS.set(ZERO);
+    let r = f();
+    let c = COUNTERS.get();
+    let stats = AllocStats {
+        count: c.count,
+        bytes: c.bytes,
+        peak: c.peak.max(0) as u64,
+    };
+    (r, Some(stats))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fmt_bytes;
+
+    #[test]
+    fn it_works() {
+        let (n, stats) = measure(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(1000);
+            v.extend(0..1000);
+            drop(v);
+            "x".repeat(100).len()
+        });
+        assert_eq!(n, 100);
+        assert_eq!(stats.is_some(), ENABLED);
+        if let Some(s) = stats {
+            assert_eq!(s.count, 2);
+            assert_eq!(s.bytes, 8100);
+            assert_eq!(s.peak, 8000);
+        }
+
+        assert_eq!(fmt_bytes(512), "512B");
+        assert_eq!(fmt_bytes(1536), "1.5KiB");
+        assert_eq!(fmt_bytes(3 << 20), "3.0MiB");
+    }
+}
@@ -0,0 +1,107 @@
+use crate::alloc::AllocStats;
+use anyhow::Result;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+// Result and solve time of a single puzzle part.
+#[derive(Debug)]
+pub struct Part {
+    pub result: Result<String>,
+    // None if the part was solved together with the previous one,
+    // and its time is included in that of the previous part.
+    pub elapsed: Option<Duration>,
+}
+
+impl Part {
+    // Evaluates f and records how long it took.
+    pub fn eval<T: Display>(f: impl FnOnce() -> T) -> Self {
+        Self::try_eval(|| Ok(f()))
+    }
+
+    pub fn try_eval<T: Display>(f: impl FnOnce() -> Result<T>) -> Self {
+        let now = Instant::now();
+        let result = f().map(|x| x.to_string());
+        Self {
+            result,
+            elapsed: Some(now.elapsed()),
+        }
+    }
+
+    pub fn value(&self) -> Option<&str> {
+        self.result.as_ref().ok().map(|s| s.as_str())
+    }
+}
+
+// Answers for both parts of a day.
+// A part is None if it was not asked for, or the puzzle has only one part.
+#[derive(Debug)]
+pub struct Answer {
+    pub part1: Option<Part>,
+    pub part2: Option<Part>,
+
+    // Time spent parsing the input.
+    pub parse: Duration,
+
+    // Heap allocations of the whole run, with the alloc-stats feature.
+    pub alloc: Option<AllocStats>,
+}
+
+impl Answer {
+    pub fn new(part1: Part, part2: Part) -> Self {
+        Self {
+            part1: Some(part1),
+            part2: Some(part2),
+            parse: Duration::ZERO,
+            alloc: None,
+        }
+    }
+
+    pub fn single(part1: Part) -> Self {
+        Self {
+            part1: Some(part1),
+            part2: None,
+            parse: Duration::ZERO,
+            alloc: None,
+        }
+    }
+
+    pub fn eval<A: Display, B: Display>(f1: impl FnOnce() -> A, f2: impl FnOnce() -> B) -> Self {
+        Self::new(Part::eval(f1), Part::eval(f2))
+    }
+
+    // Evaluates f that computes both parts in one go.
+    // The time taken is charged to part 1, part 2 shares it.
+    pub fn joint<A: Display, B: Display>(f: impl FnOnce() -> (A, B)) -> Self {
+        Self::try_joint(|| Ok(f()))
+    }
+
+    pub fn try_joint<A: Display, B: Display>(f: impl FnOnce() -> Result<(A, B)>) -> Self {
+        let now = Instant::now();
+        let (r1, r2) = match f() {
+            Ok((a, b)) => (Ok(a.to_string()), Ok(b.to_string())),
+            Err(e) => {
+                let r2 = Err(anyhow::anyhow!("{e}"));
+                (Err(e), r2)
+            }
+        };
+        Self::new(
+            Part {
+                result: r1,
+                elapsed: Some(now.elapsed()),
+            },
+            Part {
+                result: r2,
+                elapsed: None,
+            },
+        )
+    }
+
+    pub fn solve_time(&self) -> Duration {
+        self.parts().filter_map(|(_, p)| p.elapsed).sum()
+    }
+
+    pub fn parts(&self) -> impl Iterator<Item = (usize, &Part)> {
+        let part1 = self.part1.iter().map(|p| (1, p));
+        part1.chain(self.part2.iter().map(|p| (2, p)))
+    }
+}
@@ -0,0 +1,253 @@
+use crate::select::{get_day_funcs, DaySelection};
+use anyhow::{anyhow, Result};
+use aoc24::input::InputSource;
+use aoc24::logger::Logger;
+use aoc24::{fmt_duration, runner, Answer, Context, DayFunc};
+use clap::Args;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// Time budget per day when neither the number of runs nor a budget is given.
+const DEFAULT_BUDGET: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    /// Number of timed runs per day
+    #[arg(short = 'n', long)]
+    runs: Option<usize>,
+
+    /// Time budget per day in seconds; stops at whichever limit is hit first
+    #[arg(short, long)]
+    budget: Option<f64>,
+
+    /// Number of untimed warm-up runs per day, at least one;
+    /// the first one is subject to --timeout
+    #[arg(short, long, default_value_t = 1)]
+    warmup: usize,
+
+    /// Print results as JSON
+    #[arg(long)]
+    json: bool,
+
+    #[command(flatten)]
+    days: DaySelection,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn new(mut v: Vec<Duration>) -> Option<Self> {
+        if v.is_empty() {
+            return None;
+        }
+        v.sort();
+        let n = v.len();
+        let median = if n.is_multiple_of(2) {
+            (v[n / 2 - 1] + v[n / 2]) / 2
+        } else {
+            v[n / 2]
+        };
+        let mean = v.iter().sum::<Duration>() / n as u32;
+        let var = if n > 1 {
+            let m = mean.as_secs_f64();
+            v.iter().map(|d| (d.as_secs_f64() - m).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        Some(Self {
+            runs: n,
+            min: v[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(var.sqrt()),
+        })
+    }
+
+    // Sum of stats, assuming the days are independent.
+    pub fn total<'a>(it: impl Iterator<Item = &'a Stats>) -> Self {
+        let mut t = Self {
+            runs: 0,
+            min: Duration::ZERO,
+            median: Duration::ZERO,
+            mean: Duration::ZERO,
+            stddev: Duration::ZERO,
+        };
+        let mut var = 0.0;
+        for s in it {
+            t.runs += s.runs;
+            t.min += s.min;
+            t.median += s.median;
+            t.mean += s.mean;
+            var += s.stddev.as_secs_f64().powi(2);
+        }
+        t.stddev = Duration::from_secs_f64(var.sqrt());
+        t
+    }
+}
+
+pub fn main(
+    is: &InputSource,
+    args: &BenchArgs,
+    logger: &Arc<Logger>,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    if !args.json {
+        println!(
+            "         {:>10} {:>10} {:>10} {:>10}",
+            "min", "median", "mean", "stddev"
+        );
+    }
+
+    let mut results = vec![];
+    for (day, f) in get_day_funcs(is, &args.days)? {
+        let ctx = Context::new(day, logger.clone());
+        let r = is
+            .get(day)
+            .and_then(|input| bench_day(f, &input, &ctx, args, timeout));
+        match r {
+            Ok(stats) => {
+                if !args.json {
+                    print_row(&format!("{:3}", day), &stats);
+                }
+                results.push((day, stats));
+            }
+            Err(e) => eprintln!("Day {:2}: {}", day, e),
+        }
+    }
+
+    let total = Stats::total(results.iter().map(|(_, s)| s));
+    if args.json {
+        let report = Report {
+            days: results
+                .iter()
+                .map(|&(d, s)| Record::new(Some(d), s))
+                .collect(),
+            total: Record::new(None, total),
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_row("all", &total);
+    }
+    Ok(())
+}
+
+fn bench_day(
+    f: DayFunc,
+    input: &str,
+    ctx: &Context,
+    args: &BenchArgs,
+    timeout: Option<Duration>,
+) -> Result<Stats> {
+    // The first run is isolated, so that a day that hangs can't stall the
+    // whole bench. The day is known to finish before the runs that follow.
+    let answer = runner::run_isolated(f, input.to_owned(), ctx.clone(), timeout)?;
+    check_parts(&answer)?;
+    for _ in 1..args.warmup {
+        run_once(f, input, ctx)?;
+    }
+
+    let budget = args.budget.map(Duration::from_secs_f64);
+    let (runs, budget) = match (args.runs, budget) {
+        (None, None) => (None, Some(DEFAULT_BUDGET)),
+        x => x,
+    };
+
+    let start = Instant::now();
+    let mut v = vec![];
+    loop {
+        let now = Instant::now();
+        run_once(f, input, ctx)?;
+        v.push(now.elapsed());
+
+        if runs.is_some_and(|n| v.len() >= n) || budget.is_some_and(|b| start.elapsed() >= b) {
+            break;
+        }
+    }
+    Stats::new(v).ok_or_else(|| anyhow!("no runs"))
+}
+
+// Runs f once, with a panic reported as an error of this day only.
+fn run_once(f: DayFunc, input: &str, ctx: &Context) -> Result<()> {
+    let answer = runner::catch_panic(|| f(input, ctx))?;
+    check_parts(&answer)
+}
+
+fn check_parts(answer: &Answer) -> Result<()> {
+    for (n, part) in answer.parts() {
+        if let Err(e) = &part.result {
+            return Err(anyhow!("part {}: {}", n, e));
+        }
+    }
+    Ok(())
+}
+
+fn print_row(label: &str, s: &Stats) {
+    println!(
+        "Day {}: {:>10} {:>10} {:>10} {:>10}  ({} runs)",
+        label,
+        fmt_duration(s.min),
+        fmt_duration(s.median),
+        fmt_duration(s.mean),
+        fmt_duration(s.stddev),
+        s.runs
+    );
+}
+
+#[derive(Serialize)]
+struct Report {
+    days: Vec<Record>,
+    total: Record,
+}
+
+#[derive(Serialize)]
+struct Record {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    day: Option<usize>,
+    runs: usize,
+    min_ms: f64,
+    median_ms: f64,
+    mean_ms: f64,
+    stddev_ms: f64,
+}
+
+impl Record {
+    fn new(day: Option<usize>, s: Stats) -> Self {
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        Self {
+            day,
+            runs: s.runs,
+            min_ms: ms(s.min),
+            median_ms: ms(s.median),
+            mean_ms: ms(s.mean),
+            stddev_ms: ms(s.stddev),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let ms = Duration::from_millis;
+        let s = Stats::new(vec![ms(4), ms(1), ms(3), ms(2), ms(5)]).unwrap();
+        assert_eq!(s.runs, 5);
+        assert_eq!(s.min, ms(1));
+        assert_eq!(s.median, ms(3));
+        assert_eq!(s.mean, ms(3));
+        assert!((s.stddev.as_secs_f64() * 1000.0 - 2.5f64.sqrt()).abs() < 1e-6);
+
+        let s = Stats::new(vec![ms(1), ms(4)]).unwrap();
+        assert_eq!(s.median, Duration::from_micros(2500));
+        assert!(Stats::new(vec![]).is_none());
+    }
+}
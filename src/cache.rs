@@ -0,0 +1,406 @@
+use crate::history::input_hash;
+use crate::select::DayList;
+use anyhow::{anyhow, bail, Context, Result};
+use aoc24::fmt_bytes;
+use aoc24::input::InputSource;
+use clap::Subcommand;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+// Name of the file listing the hashes of the inputs in an exported archive.
+const HASHES: &str = "hashes";
+
+#[derive(Debug, Subcommand)]
+pub enum CacheCommand {
+    /// Find cached inputs that are error responses instead of puzzle input
+    Check {
+        /// Remove the entries found
+        #[arg(long)]
+        evict: bool,
+    },
+
+    /// List the cached inputs with their size, fetch date and hash
+    List,
+
+    /// Show the details of the cached input of a day
+    Show {
+        day: usize,
+
+        /// Print the input itself
+        #[arg(short, long)]
+        raw: bool,
+    },
+
+    /// Remove cached inputs
+    Rm {
+        /// Days to remove, such as `1-5,9`
+        #[arg(required = true, value_name = "DAYS")]
+        days: Vec<DayList>,
+    },
+
+    /// Add inputs to the cache from a directory, or from an archive made by export
+    Import {
+        path: PathBuf,
+
+        /// Replace inputs that are already cached
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Write the cached inputs and their hashes into a tar archive
+    Export { archive: PathBuf },
+}
+
+pub fn main(is: &InputSource, cmd: &CacheCommand) -> Result<()> {
+    match cmd {
+        CacheCommand::Check { evict } => check(is, *evict),
+        CacheCommand::List => list(is),
+        CacheCommand::Show { day, raw } => show(is, *day, *raw),
+        CacheCommand::Rm { days } => remove(is, days),
+        CacheCommand::Import { path, force } => import(is, path, *force),
+        CacheCommand::Export { archive } => export(is, archive),
+    }
+}
+
+fn check(is: &InputSource, evict: bool) -> Result<()> {
+    let poisoned = is.poisoned();
+    if poisoned.is_empty() {
+        println!("No error responses in the {} input cache", is.year());
+    }
+    for (day, cause) in poisoned {
+        if evict {
+            is.evict(day)?;
+            println!("Day {:2}: {}, removed", day, cause);
+        } else {
+            println!("Day {:2}: {}", day, cause);
+        }
+    }
+    Ok(())
+}
+
+// Cached input of a day.
+struct Entry {
+    day: usize,
+    path: PathBuf,
+    input: String,
+    fetched: Option<SystemTime>,
+}
+
+impl Entry {
+    fn load(is: &InputSource, day: usize) -> Result<Self> {
+        let path = is
+            .cache_path(day)
+            .ok_or_else(|| anyhow!("no cache directory"))?;
+        let input = fs::read_to_string(&path)
+            .with_context(|| format!("input for day {} of {} is not cached", day, is.year()))?;
+        let fetched = fs::metadata(&path).and_then(|m| m.modified()).ok();
+        Ok(Self {
+            day,
+            path,
+            input,
+            fetched,
+        })
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "Day {:2}  {:>8}  {}  {}",
+            self.day,
+            fmt_bytes(self.input.len() as u64),
+            self.fetched.map_or_else(|| String::from("-"), fmt_time),
+            input_hash(&self.input)
+        )
+    }
+}
+
+fn entries(is: &InputSource) -> Result<Vec<Entry>> {
+    is.cached_days()
+        .into_iter()
+        .map(|day| Entry::load(is, day))
+        .collect()
+}
+
+fn list(is: &InputSource) -> Result<()> {
+    let entries = entries(is)?;
+    if entries.is_empty() {
+        println!("No cached inputs for {}", is.year());
+    }
+    for e in &entries {
+        println!("{}", e.summary());
+    }
+    Ok(())
+}
+
+fn show(is: &InputSource, day: usize, raw: bool) -> Result<()> {
+    let e = Entry::load(is, day)?;
+    if raw {
+        print!("{}", e.input);
+        return Ok(());
+    }
+    println!("Day {} of {}", day, is.year());
+    println!("  path     {}", e.path.display());
+    println!(
+        "  size     {}, {} lines",
+        fmt_bytes(e.input.len() as u64),
+        e.input.lines().count()
+    );
+    if let Some(t) = e.fetched {
+        println!("  fetched  {}", fmt_time(t));
+    }
+    println!("  hash     {}", input_hash(&e.input));
+    Ok(())
+}
+
+fn remove(is: &InputSource, days: &[DayList]) -> Result<()> {
+    let days: Vec<usize> = is
+        .cached_days()
+        .into_iter()
+        .filter(|&d| days.iter().any(|l| l.contains(d)))
+        .collect();
+    if days.is_empty() {
+        bail!("none of the days are cached for {}", is.year());
+    }
+    for day in days {
+        is.evict(day)?;
+        println!("Day {:2}: removed", day);
+    }
+    Ok(())
+}
+
+// Inputs read from a directory or an archive, by day,
+// with the hashes recorded by export if there are any.
+#[derive(Debug, Default)]
+struct Bundle {
+    inputs: BTreeMap<usize, (String, Option<SystemTime>)>,
+    hashes: Option<BTreeMap<usize, String>>,
+}
+
+impl Bundle {
+    // Adds the file called name, ignoring files that are neither inputs nor hashes.
+    fn add(&mut self, name: &str, data: String, mtime: Option<SystemTime>) -> Result<()> {
+        if name == HASHES {
+            self.hashes = Some(parse_hashes(&data)?);
+        } else if let Ok(day) = name.parse() {
+            self.inputs.insert(day, (data, mtime));
+        }
+        Ok(())
+    }
+
+    // Reads the inputs in dir, or in its folder for year as laid out by export.
+    fn from_dir(dir: &Path, year: u32) -> Result<Self> {
+        let sub = dir.join(format!("aoc{}", year));
+        let dir = if sub.is_dir() { &sub } else { dir };
+        let mut b = Bundle::default();
+        for e in fs::read_dir(dir).with_context(|| format!("reading {:?}", dir))? {
+            let e = e?;
+            let (path, name) = (e.path(), e.file_name());
+            let (Some(name), true) = (name.to_str(), e.file_type()?.is_file()) else {
+                continue;
+            };
+            let data = fs::read_to_string(&path).with_context(|| format!("reading {:?}", path))?;
+            b.add(name, data, e.metadata()?.modified().ok())?;
+        }
+        Ok(b)
+    }
+
+    fn from_archive(path: &Path, year: u32) -> Result<Self> {
+        let f = File::open(path).with_context(|| format!("opening {:?}", path))?;
+        let folder = format!("aoc{}", year);
+        let mut b = Bundle::default();
+        for e in tar::Archive::new(f).entries()? {
+            let mut e = e?;
+            let p = e.path()?.into_owned();
+            let parent = p.parent().and_then(|p| p.to_str()).unwrap_or_default();
+            let Some(name) = p.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if parent != folder {
+                continue;
+            }
+            let mtime = e.header().mtime().ok();
+            let mtime = mtime.map(|t| SystemTime::UNIX_EPOCH + Duration::from_secs(t));
+            let mut data = String::new();
+            e.read_to_string(&mut data)
+                .with_context(|| format!("reading {:?} in {:?}", p, path))?;
+            b.add(name, data, mtime)?;
+        }
+        Ok(b)
+    }
+
+    // Checks the input of day against the hash recorded for it.
+    fn verify(&self, day: usize, input: &str) -> Result<()> {
+        match self.hashes.as_ref().and_then(|h| h.get(&day)) {
+            Some(h) if *h != input_hash(input) => bail!("does not match its hash {}", h),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn parse_hashes(s: &str) -> Result<BTreeMap<usize, String>> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let (day, hash) = l
+                .split_once(' ')
+                .ok_or_else(|| anyhow!("invalid line in {}: {:?}", HASHES, l))?;
+            Ok((day.parse()?, hash.trim().to_owned()))
+        })
+        .collect()
+}
+
+fn import(is: &InputSource, path: &Path, force: bool) -> Result<()> {
+    let bundle = if path.is_dir() {
+        Bundle::from_dir(path, is.year())?
+    } else {
+        Bundle::from_archive(path, is.year())?
+    };
+    if bundle.inputs.is_empty() {
+        bail!("no inputs for {} in {:?}", is.year(), path);
+    }
+
+    let mut nfail = 0;
+    for (&day, (input, mtime)) in &bundle.inputs {
+        let r = bundle.verify(day, input).and_then(|_| {
+            match Entry::load(is, day) {
+                Ok(e) if e.input == *input => return Ok("already cached"),
+                Ok(_) if !force => return Ok("differs from the cached one, skipped"),
+                _ => {}
+            }
+            let p = is.store(day, input)?;
+            if let Some(t) = mtime {
+                // The fetch date is shown from the modification time.
+                File::options().write(true).open(&p)?.set_modified(*t)?;
+            }
+            Ok("imported")
+        });
+        match r {
+            Ok(what) => println!("Day {:2}: {}", day, what),
+            Err(e) => {
+                println!("Day {:2}: {:#}", day, e);
+                nfail += 1;
+            }
+        }
+    }
+    if nfail > 0 {
+        bail!("{} input(s) not imported", nfail);
+    }
+    Ok(())
+}
+
+fn export(is: &InputSource, archive: &Path) -> Result<()> {
+    let entries = entries(is)?;
+    if entries.is_empty() {
+        bail!("no cached inputs for {}", is.year());
+    }
+    write_archive(archive, is.year(), &entries)?;
+    println!("{} input(s) exported to {:?}", entries.len(), archive);
+    Ok(())
+}
+
+// Writes entries in the folder for year of a tar archive,
+// along with a file listing their hashes.
+fn write_archive(path: &Path, year: u32, entries: &[Entry]) -> Result<()> {
+    let f = File::create(path).with_context(|| format!("creating {:?}", path))?;
+    let folder = format!("aoc{}", year);
+    let mut tar = tar::Builder::new(f);
+    let mut hashes = String::new();
+    for e in entries {
+        let name = format!("{}/{}", folder, e.day);
+        tar.append_path_with_name(&e.path, &name)
+            .with_context(|| format!("adding {:?}", e.path))?;
+        hashes.push_str(&format!("{} {}\n", e.day, input_hash(&e.input)));
+    }
+
+    let mut header = tar::Header::new_gnu();
+    header.set_size(hashes.len() as u64);
+    header.set_mode(0o644);
+    let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
+    header.set_mtime(now.as_secs());
+    header.set_cksum();
+    tar.append_data(
+        &mut header,
+        format!("{}/{}", folder, HASHES),
+        hashes.as_bytes(),
+    )?;
+    tar.into_inner()?;
+    Ok(())
+}
+
+// Formats t as a UTC date and time, such as `2024-12-01 05:00`.
+fn fmt_time(t: SystemTime) -> String {
+    let secs = t
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let (days, rem) = ((secs / 86400) as i64, secs % 86400);
+
+    // Civil date from the number of days since 1970-01-01,
+    // with years starting in March so that leap days come last.
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        y,
+        m,
+        d,
+        rem / 3600,
+        rem % 3600 / 60
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn it_works() {
+        let at = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        assert_eq!(fmt_time(at(0)), "1970-01-01 00:00");
+        assert_eq!(fmt_time(at(1733029200)), "2024-12-01 05:00");
+        assert_eq!(fmt_time(at(951782400)), "2000-02-29 00:00");
+
+        let dir = std::env::temp_dir().join(format!("aoc-cache-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let entries: Vec<Entry> = [(3, "1 2\n"), (14, "p=0,4 v=3,-3\n")]
+            .into_iter()
+            .map(|(day, input)| {
+                let path = dir.join(day.to_string());
+                fs::write(&path, input).unwrap();
+                Entry {
+                    day,
+                    path,
+                    input: input.to_owned(),
+                    fetched: None,
+                }
+            })
+            .collect();
+
+        let archive = dir.join("inputs.tar");
+        write_archive(&archive, 2024, &entries).unwrap();
+        let b = Bundle::from_archive(&archive, 2024).unwrap();
+        assert_eq!(b.inputs.keys().copied().collect::<Vec<_>>(), [3, 14]);
+        assert_eq!(b.inputs[&14].0, "p=0,4 v=3,-3\n");
+        assert!(b.inputs[&3].1.is_some());
+        assert!(b.verify(3, "1 2\n").is_ok());
+        assert!(b.verify(3, "1 3\n").is_err());
+        assert!(Bundle::from_archive(&archive, 2023)
+            .unwrap()
+            .inputs
+            .is_empty());
+
+        let b = Bundle::from_dir(&dir, 2024).unwrap();
+        assert_eq!(b.inputs.len(), 2);
+        assert!(b.hashes.is_none());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
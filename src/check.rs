@@ -0,0 +1,121 @@
+use crate::answer::Part;
+use anyhow::{anyhow, Context, Result};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Expected answers keyed by (day, part).
+//
+// The file format is one answer per line: `<day> <part> <answer>`.
+pub struct Expected {
+    path: PathBuf,
+    m: BTreeMap<(usize, usize), String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    Unknown,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail(want) => write!(f, "FAIL, want {}", want),
+            Status::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl Expected {
+    pub fn load(path: &Path) -> Result<Self> {
+        let m = match fs::read_to_string(path) {
+            Ok(s) => parse(&s).with_context(|| format!("reading answers from {:?}", path))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e).with_context(|| format!("reading {:?}", path)),
+        };
+        Ok(Self {
+            path: path.to_owned(),
+            m,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, format(&self.m))
+            .with_context(|| format!("writing answers to {:?}", self.path))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn check(&self, day: usize, part: usize, p: &Part) -> Status {
+        let Some(want) = self.m.get(&(day, part)) else {
+            return Status::Unknown;
+        };
+        match p.value() {
+            Some(got) if got == want => Status::Pass,
+            _ => Status::Fail(want.clone()),
+        }
+    }
+
+    // Parts of day with an expected answer, and their answers.
+    pub fn parts(&self, day: usize) -> impl Iterator<Item = (usize, &str)> {
+        self.m
+            .range((day, 0)..=(day, usize::MAX))
+            .map(|(&(_, n), v)| (n, v.as_str()))
+    }
+
+    pub fn record(&mut self, day: usize, part: usize, p: &Part) {
+        if let Some(v) = p.value() {
+            self.m.insert((day, part), v.to_owned());
+        }
+    }
+}
+
+fn parse(s: &str) -> Result<BTreeMap<(usize, usize), String>> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(n, l)| {
+            let mut it = l.splitn(3, ' ');
+            let mut num = || -> Option<usize> { it.next()?.parse().ok() };
+            let (Some(day), Some(part)) = (num(), num()) else {
+                return Err(anyhow!("line {}: invalid day or part", n + 1));
+            };
+            let v = it
+                .next()
+                .ok_or_else(|| anyhow!("line {}: missing answer", n + 1))?;
+            Ok(((day, part), v.to_owned()))
+        })
+        .collect()
+}
+
+fn format(m: &BTreeMap<(usize, usize), String>) -> String {
+    m.iter()
+        .map(|((day, part), v)| format!("{} {} {}\n", day, part, v))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let input = "1 1 11\n1 2 31\n\n17 1 4,6,3,5\n";
+        let m = parse(input).unwrap();
+        assert_eq!(m.len(), 3);
+        assert_eq!(m[&(17, 1)], "4,6,3,5");
+        assert_eq!(format(&m), input.replace("\n\n", "\n"));
+
+        assert!(parse("1 x 11").is_err());
+        assert!(parse("1 1").is_err());
+    }
+}
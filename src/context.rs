@@ -0,0 +1,45 @@
+use crate::logger::{Level, Logger};
+use crate::solution::Parts;
+use std::fmt;
+use std::sync::Arc;
+
+// Logs a message through a Context at a Level, such as
+// `log!(ctx, Debug, "{} sec", nsec)`.
+#[macro_export]
+macro_rules! log {
+    ($ctx:expr, $level:ident, $($arg:tt)+) => {
+        $ctx.log($crate::logger::Level::$level, format_args!($($arg)+))
+    };
+}
+
+// What a solver gets to know about the run it is part of.
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    pub day: usize,
+    pub parts: Parts,
+    logger: Arc<Logger>,
+}
+
+impl Context {
+    pub fn new(day: usize, logger: Arc<Logger>) -> Self {
+        Self {
+            day,
+            parts: Parts::Both,
+            logger,
+        }
+    }
+
+    pub fn with_parts(mut self, parts: Parts) -> Self {
+        self.parts = parts;
+        self
+    }
+
+    // Whether messages at level are logged, to skip building expensive ones.
+    pub fn enabled(&self, level: Level) -> bool {
+        self.logger.enabled(self.day, level)
+    }
+
+    pub fn log(&self, level: Level, args: fmt::Arguments) {
+        self.logger.log(self.day, level, args)
+    }
+}
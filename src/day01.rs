@@ -1,8 +1,26 @@
+use crate::context::Context;
+use crate::solution::Solution;
+use anyhow::Result;
 use std::collections::HashMap;
+use std::fmt::Display;
 use std::iter::zip;
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    Ok(format!("{} {}", star1(input), star2(input)))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = (Vec<usize>, Vec<usize>);
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        Ok(get_lists(input))
+    }
+
+    fn part1((v0, v1): &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star1(v0, v1))
+    }
+
+    fn part2((v0, v1): &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star2(v0, v1))
+    }
 }
 
 fn get_lists(input: &str) -> (Vec<usize>, Vec<usize>) {
@@ -17,21 +35,18 @@ fn get_lists(input: &str) -> (Vec<usize>, Vec<usize>) {
     (v0, v1)
 }
 
-fn star1(input: &str) -> usize {
-    let (v0, v1) = get_lists(input);
+fn star1(v0: &[usize], v1: &[usize]) -> usize {
     zip(v0, v1)
-        .map(|(l, r)| if l < r { r - l } else { l - r })
+        .map(|(l, r)| l.abs_diff(*r))
         .sum()
 }
 
-fn star2(input: &str) -> usize {
-    let (v0, v1) = get_lists(input);
-
+fn star2(v0: &[usize], v1: &[usize]) -> usize {
     let mut occurrences = HashMap::new();
     for y in v1 {
         occurrences.entry(y).and_modify(|n| *n += 1).or_insert(1);
     }
-    v0.into_iter()
+    v0.iter()
         .map(|x| x * occurrences.get(&x).unwrap_or(&0))
         .sum()
 }
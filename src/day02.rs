@@ -1,9 +1,25 @@
+use crate::context::Context;
+use crate::solution::Solution;
 use crate::util;
+use anyhow::Result;
+use std::fmt::Display;
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let s1 = count_safe(input);
-    let s2 = count_safe_dampened(input);
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = &'a str;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(count_safe(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(count_safe_dampened(input))
+    }
 }
 
 fn count_safe(input: &str) -> usize {
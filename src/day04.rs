@@ -1,26 +1,30 @@
-use crate::grid::{pt, Grid, Point};
+use crate::context::Context;
+use crate::grid::{Dir8, Grid, Point, DIRS8};
+use crate::solution::Solution;
+use anyhow::Result;
+use std::fmt::Display;
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let grid = Grid::parse(input)?;
-    let s1 = star1(&grid);
-    let s2 = star2(&grid);
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Grid<u8>;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        Grid::parse(input)
+    }
+
+    fn part1(grid: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star1(grid))
+    }
+
+    fn part2(grid: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star2(grid))
+    }
 }
 
 fn star1(grid: &Grid<u8>) -> usize {
-    let dirs = [
-        pt(-1, -1),
-        pt(0, -1),
-        pt(1, -1),
-        pt(1, 0),
-        pt(1, 1),
-        pt(0, 1),
-        pt(-1, 1),
-        pt(-1, 0),
-    ];
-
     grid.positions()
-        .flat_map(|p| dirs.map(|d| (p, d)))
+        .flat_map(|p| DIRS8.iter().map(move |d| (p, d.step(1))))
         .filter(|(p, d)| is_grid_string(grid, *p, *d, "XMAS"))
         .count()
 }
@@ -51,10 +55,10 @@ fn is_x_mas(grid: &Grid<u8>, p: Point) -> bool {
         return false;
     }
 
-    let at = |dx, dy| -> Option<&u8> { grid.get(p + Point::new(dx, dy)) };
+    let at = |d: Dir8| -> Option<&u8> { grid.get(p + d.step(1)) };
 
-    let d1 = (at(-1, -1), at(1, 1));
-    let d2 = (at(1, -1), at(-1, 1));
+    let d1 = (at(Dir8::NorthWest), at(Dir8::SouthEast));
+    let d2 = (at(Dir8::NorthEast), at(Dir8::SouthWest));
     (d1 == (Some(&b'M'), Some(&b'S')) || d1 == (Some(&b'S'), Some(&b'M')))
         && (d2 == (Some(&b'M'), Some(&b'S')) || d2 == (Some(&b'S'), Some(&b'M')))
 }
@@ -1,13 +1,28 @@
+use crate::context::Context;
+use crate::solution::Solution;
 use anyhow::{anyhow, Result};
 use std::cmp::Ordering;
+use std::fmt::Display;
 
-pub fn run(input: &str) -> Result<String> {
-    let pj = parse_print_job(input)?;
-    let (s1, s2) = stars(&pj);
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = PrintJob;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        parse_print_job(input)
+    }
+
+    fn part1(pj: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star1(pj))
+    }
+
+    fn part2(pj: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star2(pj))
+    }
 }
 
-struct PrintJob {
+pub struct PrintJob {
     ord: Vec<(i32, i32)>,
     upd: Vec<Vec<i32>>,
 }
@@ -43,27 +58,21 @@ fn parse_print_job(input: &str) -> Result<PrintJob> {
     Ok(pj)
 }
 
-fn stars(pj: &PrintJob) -> (usize, usize) {
-    let mut pages_after = vec![0u128; 100];
-    for &(x, y) in pj.ord.iter() {
-        pages_after[x as usize] |= 1u128 << y;
-    }
+fn star1(pj: &PrintJob) -> usize {
+    let pages_after = pages_after(pj);
+    pj.upd
+        .iter()
+        .filter(|pr| is_ordered(&pages_after, pr))
+        .map(|pr| pr[pr.len() / 2] as usize)
+        .sum()
+}
 
+fn star2(pj: &PrintJob) -> usize {
+    let pages_after = pages_after(pj);
     pj.upd
         .iter()
+        .filter(|pr| !is_ordered(&pages_after, pr))
         .map(|pr| {
-            let order_ok = pr
-                .iter()
-                .scan(0u128, |state, n| {
-                    let pred = *state;
-                    *state |= 1u128 << n;
-                    Some((pred, n))
-                })
-                .all(|(preds, n)| pages_after[*n as usize] & preds == 0);
-
-            if order_ok {
-                return (true, pr[pr.len() / 2] as usize);
-            }
             let mut v = pr.clone();
             v.sort_by(|&x, &y| {
                 let x_less = pages_after[x as usize] & (1 << y) == 0;
@@ -77,14 +86,26 @@ fn stars(pj: &PrintJob) -> (usize, usize) {
                     }
                 }
             });
-            (false, v[v.len() / 2] as usize)
+            v[v.len() / 2] as usize
         })
-        .fold((0, 0), |mut acc, (is_ok, v)| {
-            if is_ok {
-                acc.0 += v;
-            } else {
-                acc.1 += v;
-            }
-            acc
+        .sum()
+}
+
+// Bit set of the pages that must come after each page.
+fn pages_after(pj: &PrintJob) -> Vec<u128> {
+    let mut pages_after = vec![0u128; 100];
+    for &(x, y) in pj.ord.iter() {
+        pages_after[x as usize] |= 1u128 << y;
+    }
+    pages_after
+}
+
+fn is_ordered(pages_after: &[u128], pr: &[i32]) -> bool {
+    pr.iter()
+        .scan(0u128, |state, n| {
+            let pred = *state;
+            *state |= 1u128 << n;
+            Some((pred, n))
         })
+        .all(|(preds, n)| pages_after[*n as usize] & preds == 0)
 }
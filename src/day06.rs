@@ -1,19 +1,37 @@
+use crate::context::Context;
 use crate::grid::{Dir, Grid, Point};
+use crate::solution::Solution;
 use anyhow::{anyhow, Result};
+use std::fmt::Display;
 
-pub fn run(input: &str) -> Result<String> {
-    let grid = Grid::parse(input)?;
-    let start = find_start(&grid).ok_or_else(|| anyhow!("no guard"))?;
-    let (s1, s2) = stars(&grid, start, Dir::North);
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = (Grid<u8>, Point);
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        let grid = Grid::parse(input)?;
+        let start = find_start(&grid).ok_or_else(|| anyhow!("no guard"))?;
+        Ok((grid, start))
+    }
+
+    fn part1((grid, start): &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(walk(grid, *start, Dir::North, None).1)
+    }
+
+    fn part2((grid, start): &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(count_obstacles(grid, *start, Dir::North))
+    }
 }
 
 fn find_start(grid: &Grid<u8>) -> Option<Point> {
     grid.positions().find(|&p| grid.get(p) == Some(&b'^'))
 }
 
-fn stars(grid: &Grid<u8>, start: Point, dir: Dir) -> (usize, usize) {
-    let (visited, exit_count, _) = walk(grid, start, dir, None);
+// Number of places where a new obstacle would get the guard stuck in a loop.
+// Only the cells on the original route are worth trying.
+fn count_obstacles(grid: &Grid<u8>, start: Point, dir: Dir) -> usize {
+    let (visited, _, _) = walk(grid, start, dir, None);
 
     let mut num_obstacles = 0;
     for p in grid.positions() {
@@ -26,7 +44,7 @@ fn stars(grid: &Grid<u8>, start: Point, dir: Dir) -> (usize, usize) {
         }
     }
 
-    (exit_count, num_obstacles)
+    num_obstacles
 }
 
 fn walk(
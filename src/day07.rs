@@ -1,20 +1,34 @@
+use crate::context::Context;
+use crate::solution::Solution;
 use anyhow::{anyhow, Result};
+use std::fmt::Display;
 
-pub fn run(input: &str) -> Result<String> {
-    let data = parse_data(input)?;
-    let s1: i64 = data
-        .iter()
-        .filter_map(|l| l.is_add_mul().then_some(l.l))
-        .sum();
-    let s2: i64 = data
-        .iter()
-        .filter_map(|l| l.is_add_mul_concat().then_some(l.l))
-        .sum();
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Vec<Line>;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        parse_data(input)
+    }
+
+    fn part1(data: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(data
+            .iter()
+            .filter_map(|l| l.is_add_mul().then_some(l.l))
+            .sum::<i64>())
+    }
+
+    fn part2(data: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(data
+            .iter()
+            .filter_map(|l| l.is_add_mul_concat().then_some(l.l))
+            .sum::<i64>())
+    }
 }
 
 #[derive(Debug)]
-struct Line {
+pub struct Line {
     l: i64,
     r: Vec<i64>,
 }
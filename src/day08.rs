@@ -1,12 +1,28 @@
+use crate::context::Context;
 use crate::grid::{Grid, Point};
+use crate::solution::Solution;
+use anyhow::Result;
 use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let grid = Grid::parse(input)?;
-    let antennas = find_antennas(&grid);
-    let s1 = star(&grid, &antennas, 1);
-    let s2 = star(&grid, &antennas, 2);
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = (Grid<u8>, HashMap<u8, Vec<Point>>);
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        let grid = Grid::parse(input)?;
+        let antennas = find_antennas(&grid);
+        Ok((grid, antennas))
+    }
+
+    fn part1((grid, antennas): &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star(grid, antennas, 1))
+    }
+
+    fn part2((grid, antennas): &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star(grid, antennas, 2))
+    }
 }
 
 fn star(grid: &Grid<u8>, antennas: &HashMap<u8, Vec<Point>>, star: i32) -> usize {
@@ -1,15 +1,31 @@
+use crate::context::Context;
+use crate::solution::Solution;
+use anyhow::Result;
 use std::collections::BTreeMap;
+use std::fmt::Display;
 use std::iter;
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let s1 = star1(input);
-    let s2 = star2(input);
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = &'a str;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star1(input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star2(input))
+    }
 }
 
 fn star1(input: &str) -> usize {
     let m0 = diskmap(input)
-        .flat_map(|(v, l)| iter::repeat(v).take(l))
+        .flat_map(|(v, l)| iter::repeat_n(v, l))
         .collect::<Vec<_>>();
 
     let it_free = m0
@@ -102,7 +118,8 @@ mod test {
 
     #[test]
     fn it_works() {
-        let input = "2333133121414131402";
+        let input = crate::input::example(2024, 9, 1).unwrap();
+        let input = input.trim();
         assert_eq!(star1(input), 1928);
         assert_eq!(star2(input), 2858);
     }
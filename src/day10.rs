@@ -1,36 +1,62 @@
-use crate::grid::{Grid, Point, STEPS};
-use std::collections::HashMap;
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::grid::{Grid, Point};
+use crate::search::{self, Search};
+use crate::solution::Solution;
+use anyhow::Result;
+use std::fmt::Display;
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let grid = Grid::parse(input)?;
-    let (s1, s2) = stars(&grid);
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Grid<u8>;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        Grid::parse(input)
+    }
+
+    fn part1(grid: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(trails(grid).map(|(_, peaks)| peaks.len()).sum::<usize>())
+    }
+
+    fn part2(grid: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(trails(grid).map(|(s, peaks)| rating(&s, &peaks)).sum::<usize>())
+    }
+
+    // Both parts need the same searches from the trailheads.
+    fn both(grid: &Self::Input<'_>, _: &Context) -> Answer {
+        Answer::joint(|| {
+            trails(grid).fold((0, 0), |(acc1, acc2), (s, peaks)| {
+                (acc1 + peaks.len(), acc2 + rating(&s, &peaks))
+            })
+        })
+    }
 }
 
-fn stars(grid: &Grid<u8>) -> (usize, usize) {
+// Search from each trailhead, with the peaks reachable from it.
+fn trails(grid: &Grid<u8>) -> impl Iterator<Item = (Search<Point>, Vec<Point>)> + '_ {
     grid.positions()
-        .map(|p| trailhead_score(grid, p))
-        .fold((0, 0), |(acc1, acc2), (n1, n2)| (acc1 + n1, acc2 + n2))
+        .filter(|&p| grid.get(p) == Some(&b'0'))
+        .map(|p| trailhead(grid, p))
 }
 
-fn trailhead_score(grid: &Grid<u8>, p: Point) -> (usize, usize) {
-    if grid.get(p) != Some(&b'0') {
-        return (0, 0);
-    }
-    let mut work = vec![(p, b'0')];
-    let mut peaks = HashMap::new();
-    while let Some((p, h)) = work.pop() {
-        let h = h + 1;
-        for &s in STEPS.iter() {
-            let q = p + s;
-            if grid.get(q) == Some(&h) {
-                if h == b'9' {
-                    peaks.entry(q).and_modify(|c| *c += 1).or_insert(1);
-                } else {
-                    work.push((q, h));
-                }
-            }
-        }
-    }
-    (peaks.len(), peaks.values().sum())
+fn trailhead(grid: &Grid<u8>, p: Point) -> (Search<Point>, Vec<Point>) {
+    // Trails climb one step at a time, so all the trails to a peak are shortest paths.
+    let up = |&p: &Point| {
+        let h = grid.get(p).map_or(0, |h| h + 1);
+        grid.neighbors4(p).filter(move |&q| grid.get(q) == Some(&h))
+    };
+    let s = search::bfs([p], up, |_| false);
+    let peaks = s
+        .distances()
+        .keys()
+        .copied()
+        .filter(|&q| grid.get(q) == Some(&b'9'))
+        .collect();
+    (s, peaks)
+}
+
+// Number of distinct trails from the trailhead of s to peaks.
+fn rating(s: &Search<Point>, peaks: &[Point]) -> usize {
+    peaks.iter().map(|q| s.count_paths(q)).sum()
 }
@@ -1,14 +1,29 @@
+use crate::context::Context;
+use crate::solution::Solution;
+use anyhow::Result;
 use std::collections::HashMap;
+use std::fmt::Display;
 use std::iter;
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let stones = parse_stones(input)?;
-    let s1 = blink_stones(&stones, 25);
-    let s2 = blink_stones(&stones, 75);
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Vec<u64>;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        parse_stones(input)
+    }
+
+    fn part1(stones: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(blink_stones(stones, 25))
+    }
+
+    fn part2(stones: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(blink_stones(stones, 75))
+    }
 }
 
-fn parse_stones(input: &str) -> anyhow::Result<Vec<u64>> {
+fn parse_stones(input: &str) -> Result<Vec<u64>> {
     Ok(input
         .split_ascii_whitespace()
         .map(|s| s.parse::<u64>())
@@ -1,20 +1,35 @@
+use crate::context::Context;
 use crate::grid::Point;
+use crate::solution::Solution;
 use anyhow::{anyhow, bail, Result};
+use std::fmt::Display;
 
-pub fn run(input: &str) -> Result<String> {
-    let machines = parse_machines(input)?;
-    for m in &machines {
-        if m.a.x * m.b.y == m.a.y * m.b.x {
-            println!("{m:?} is colinear");
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Vec<Machine>;
+
+    fn parse<'a>(input: &'a str, ctx: &Context) -> Result<Self::Input<'a>> {
+        let machines = parse_machines(input)?;
+        for m in &machines {
+            if m.a.x * m.b.y == m.a.y * m.b.x {
+                crate::log!(ctx, Info, "{m:?} is colinear");
+            }
         }
+        Ok(machines)
+    }
+
+    fn part1(machines: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(min_tokens_shift(machines, 0))
+    }
+
+    fn part2(machines: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(min_tokens_shift(machines, STAR_2_SHIFT))
     }
-    let s1 = min_tokens_shift(&machines, 0);
-    let s2 = min_tokens_shift(&machines, STAR_2_SHIFT);
-    Ok(format!("{s1} {s2}"))
 }
 
 #[derive(Copy, Clone, Debug)]
-struct Machine {
+pub struct Machine {
     a: Point,
     b: Point,
     prize: Point,
@@ -1,17 +1,31 @@
+use crate::context::Context;
 use crate::grid::{pt, Grid, Point};
-use crate::Cli;
+use crate::logger::Level;
+use crate::solution::Solution;
 use anyhow::{anyhow, Result};
 use std::cmp::Ordering;
+use std::fmt::Display;
 
-pub fn run(input: &str) -> Result<String> {
-    let robots = input.lines().map(parse_robot).collect::<Result<Vec<_>>>()?;
-    let s1 = safety_factor(&robots, 101, 103, 100);
-    let s2 = xmas_iter(&robots, 101, 103);
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Vec<Robot>;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        input.lines().map(parse_robot).collect()
+    }
+
+    fn part1(robots: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(safety_factor(robots, 101, 103, 100))
+    }
+
+    fn part2(robots: &Self::Input<'_>, ctx: &Context) -> Result<impl Display> {
+        Ok(xmas_iter(robots, 101, 103, ctx))
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
-struct Robot {
+pub struct Robot {
     p: Point,
     v: Point,
 }
@@ -76,8 +90,7 @@ fn quadrant(p: Point, hx: i32, hy: i32) -> Option<usize> {
     Some(ix + iy)
 }
 
-fn xmas_iter(robots: &[Robot], dx: i32, dy: i32) -> usize {
-    let verbose = Cli::global().verbose;
+fn xmas_iter(robots: &[Robot], dx: i32, dy: i32, ctx: &Context) -> usize {
     let mut robots = robots.to_vec();
     let mut nsec = 0;
     loop {
@@ -96,14 +109,13 @@ fn xmas_iter(robots: &[Robot], dx: i32, dy: i32) -> usize {
         }
         nsec += 1;
         if is_xmas_tree(&robots) {
-            if verbose {
-                println!("{nsec} sec");
-                print_robots(&robots, dx, dy);
+            if ctx.enabled(Level::Debug) {
+                crate::log!(ctx, Debug, "{nsec} sec\n{}", draw_robots(&robots, dx, dy));
             }
             return nsec;
         }
-        if verbose && nsec % 1000 == 0 {
-            println!("{nsec} sec");
+        if nsec % 1000 == 0 {
+            crate::log!(ctx, Trace, "{nsec} sec");
         }
     }
 }
@@ -143,10 +155,11 @@ fn is_xmas_tree(robots: &[Robot]) -> bool {
     true
 }
 
-fn print_robots(robots: &[Robot], dx: i32, dy: i32) {
+fn draw_robots(robots: &[Robot], dx: i32, dy: i32) -> String {
     let mut grid = Grid::new((dx, dy), b'.');
     map_robots(&mut grid, robots);
-    grid.show()
+    let rows: Vec<_> = grid.rows().map(String::from_utf8_lossy).collect();
+    rows.join("\n")
 }
 
 #[cfg(test)]
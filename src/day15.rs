@@ -1,21 +1,31 @@
+use crate::context::Context;
 use crate::grid::{Grid, Point};
+use crate::solution::Solution;
 use anyhow::{anyhow, Result};
+use std::fmt::Display;
 use std::iter::successors;
 
-pub fn run(input: &str) -> Result<String> {
-    let (s1, s2) = stars(input)?;
-    Ok(format!("{s1} {s2}"))
-}
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = (Grid<u8>, Point, &'a str);
 
-fn stars(input: &str) -> Result<(usize, usize)> {
-    let (maps, moves) = input
-        .split_once("\n\n")
-        .ok_or_else(|| anyhow!("input: missing separator"))?;
-    let (grid, start) = parse_map(maps)?;
-    let (wgrid, wstart) = widen(&grid, start);
-    let s1 = star(grid, start, moves);
-    let s2 = star(wgrid, wstart, moves);
-    Ok((s1, s2))
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        let (maps, moves) = input
+            .split_once("\n\n")
+            .ok_or_else(|| anyhow!("input: missing separator"))?;
+        let (grid, start) = parse_map(maps)?;
+        Ok((grid, start, moves))
+    }
+
+    fn part1((grid, start, moves): &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star(grid.clone(), *start, moves))
+    }
+
+    fn part2((grid, start, moves): &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        let (wgrid, wstart) = widen(grid, *start);
+        Ok(star(wgrid, wstart, moves))
+    }
 }
 
 fn star(mut grid: Grid<u8>, start: Point, moves: &str) -> usize {
@@ -207,6 +217,12 @@ fn moves_iter(moves: &str) -> impl Iterator<Item = Point> + use<'_> {
 mod test {
     use super::*;
 
+    fn stars(input: &str) -> Result<(usize, usize)> {
+        let (grid, start, moves) = Day::parse(input, &Context::default())?;
+        let (wgrid, wstart) = widen(&grid, start);
+        Ok((star(grid, start, moves), star(wgrid, wstart, moves)))
+    }
+
     #[test]
     fn it_works() {
         let sample1 = r#"
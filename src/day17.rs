@@ -1,13 +1,25 @@
-use crate::Cli;
+use crate::context::Context;
+use crate::solution::Solution;
 use anyhow::{anyhow, Result};
 use std::collections::VecDeque;
+use std::fmt::Display;
 
-pub fn run(input: &str) -> Result<String> {
-    let (cpu, prog) = parse_input(input)?;
-    let s1 = run1(&cpu, &prog);
+pub struct Day;
 
-    let s2 = run2(&prog);
-    Ok(format!("{s1} {s2}"))
+impl Solution for Day {
+    type Input<'a> = (CpuState, Vec<u8>);
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        parse_input(input)
+    }
+
+    fn part1((cpu, prog): &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(run1(cpu, prog))
+    }
+
+    fn part2((_, prog): &Self::Input<'_>, ctx: &Context) -> Result<impl Display> {
+        Ok(run2(prog, ctx))
+    }
 }
 
 fn run1(cpu: &CpuState, prog: &[u8]) -> String {
@@ -36,11 +48,7 @@ struct Node {
     check_mask: Register,
 }
 
-fn run2(prog: &[u8]) -> String {
-    run2_impl(prog, Cli::global().verbose)
-}
-
-fn run2_impl(prog: &[u8], verbose: bool) -> String {
+fn run2(prog: &[u8], ctx: &Context) -> String {
     let prog_bits = prog.iter().rev().fold(0, |acc, &d| (acc << 3) | (d as u64));
     let prog_mask = (1 << (prog.len() * 3)) - 1;
 
@@ -55,16 +63,14 @@ fn run2_impl(prog: &[u8], verbose: bool) -> String {
     }
 
     let show = |n: &Node, bits: Register, ok: bool| {
-        if verbose {
-            print!("{0:16} {0:20o}", n.a);
-            if ok {
-                println!(" ok");
-            } else {
-                let mg = bits & n.check_mask;
-                let mw = prog_bits & n.check_mask;
-                let digits = (n.check_mask.count_ones() / 3) + 1;
-                println!(" {:w$o} != {:w$o}", mg, mw, w = digits as usize);
-            }
+        if ok {
+            crate::log!(ctx, Trace, "{0:16} {0:20o} ok", n.a);
+        } else {
+            let mg = bits & n.check_mask;
+            let mw = prog_bits & n.check_mask;
+            let digits = (n.check_mask.count_ones() / 3) + 1;
+            let w = digits as usize;
+            crate::log!(ctx, Trace, "{0:16} {0:20o} {1:w$o} != {2:w$o}", n.a, mg, mw);
         }
     };
 
@@ -112,7 +118,7 @@ fn oct(a: Register, prog: &[u8]) -> (u64, usize) {
 type Register = u64;
 
 #[derive(Debug, Copy, Clone, Default)]
-struct CpuState {
+pub struct CpuState {
     ip: usize,
     a: Register,
     b: Register,
@@ -263,6 +269,6 @@ Program: 0,3,5,4,3,0
         .trim();
 
         let (_, prog) = parse_input(input).unwrap();
-        assert_eq!(run2_impl(&prog, true), "117440");
+        assert_eq!(run2(&prog, &Context::default()), "117440");
     }
 }
@@ -1,68 +1,50 @@
-use crate::grid::{Grid, Point, STEPS};
-use crate::Cli;
-use std::collections::VecDeque;
+use crate::context::Context;
+use crate::grid::{Grid, Point};
+use crate::search;
+use crate::solution::Solution;
+use anyhow::{anyhow, Result};
+use std::fmt::Display;
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let s1 = star1(input, 70, 1024);
-    let s2 = match star2(input, 70, Cli::global().verbose) {
-        Some((x, y)) => format!("{x},{y}"),
-        None => String::from("failed"),
-    };
-    Ok(format!("{s1} {s2}"))
-}
+pub struct Day;
 
-fn star1(input: &str, dim: i32, len: usize) -> usize {
-    let dims = (dim + 1, dim + 1);
-    let grid = coords(input)
-        .take(len)
-        .fold(Grid::new(dims, b'.'), |mut grid, p| {
-            if let Some(c) = grid.get_mut(p) {
-                *c = b'#';
-            }
-            grid
-        });
+impl Solution for Day {
+    type Input<'a> = &'a str;
 
-    let mut reach = Grid::new(dims, NOT_REACHED);
-    flood(&grid, &mut reach);
-    reach.get(Point::new(0, 0)).copied().unwrap_or_default() as usize
-}
-
-fn star2(input: &str, dim: i32, verbose: bool) -> Option<(i32, i32)> {
-    let dims = (dim + 1, dim + 1);
-    let mut grid = Grid::new(dims, b'.');
-    let mut reach = Grid::new(dims, NOT_REACHED);
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        Ok(input)
+    }
 
-    flood(&grid, &mut reach);
+    fn part1(input: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star1(input, 70, 1024))
+    }
 
-    let start = Point::new(0, 0);
-    let mut block = None;
+    fn part2(input: &Self::Input<'_>, ctx: &Context) -> Result<impl Display> {
+        let (x, y) = star2(input, 70, ctx)
+            .ok_or_else(|| anyhow!("no byte blocks the exit"))?;
+        Ok(format!("{x},{y}"))
+    }
+}
 
-    for p in coords(input) {
-        let p_reach = *reach.get(p).unwrap();
-        if p_reach != NOT_REACHED {
-            if let Some(c) = grid.get_mut(p) {
-                *c = b'#';
-            }
-            flood(&grid, &mut reach);
-        }
-        if block.is_none() && *reach.get(start).unwrap() == NOT_REACHED {
-            block = Some(p.xy());
-        }
+fn star1(input: &str, dim: i32, len: usize) -> usize {
+    let bytes: Vec<Point> = coords(input).take(len).collect();
+    exit_distance(&bytes, dim).unwrap_or_default()
+}
 
-        if verbose {
-            println!(
-                "{:?} {} {}",
-                p.xy(),
-                p_reach,
-                if *reach.get(start).unwrap() != NOT_REACHED {
-                    "ok"
-                } else {
-                    "blocked"
-                }
-            );
+fn star2(input: &str, dim: i32, ctx: &Context) -> Option<(i32, i32)> {
+    let bytes: Vec<Point> = coords(input).collect();
+
+    // The exit is reachable after lo bytes have fallen, and blocked after hi.
+    let (mut lo, mut hi) = (0, bytes.len() + 1);
+    while hi - lo > 1 {
+        let mid = (lo + hi) / 2;
+        let dist = exit_distance(&bytes[..mid], dim);
+        crate::log!(ctx, Debug, "{} bytes: {:?}", mid, dist);
+        match dist {
+            Some(_) => lo = mid,
+            None => hi = mid,
         }
     }
-    block
+    bytes.get(hi - 1).map(|p| p.xy())
 }
 
 fn coords(input: &str) -> impl Iterator<Item = Point> + use<'_> {
@@ -72,29 +54,23 @@ fn coords(input: &str) -> impl Iterator<Item = Point> + use<'_> {
     })
 }
 
-const NOT_REACHED: u16 = u16::MAX;
-
-fn flood(grid: &Grid<u8>, vis: &mut Grid<u16>) {
-    vis.fill(NOT_REACHED);
-
-    let (dx, dy) = grid.dimensions();
-    let goal = Point::new(dx - 1, dy - 1);
-
-    let mut stk = VecDeque::from([(goal, 0u16)]);
-    while let Some((p, n)) = stk.pop_front() {
-        *vis.get_mut(p).unwrap() = n;
-        for &step in STEPS {
-            let q = p + step;
-            if grid.get(q) == Some(&b'.') {
-                if let Some(qv) = vis.get_mut(q) {
-                    if *qv == NOT_REACHED {
-                        *qv = n + 1;
-                        stk.push_back((q, n + 1));
-                    }
-                }
-            }
+// Length of the shortest path to the exit after bytes have fallen.
+fn exit_distance(bytes: &[Point], dim: i32) -> Option<usize> {
+    let mut grid = Grid::new((dim + 1, dim + 1), b'.');
+    for &p in bytes {
+        if let Some(c) = grid.get_mut(p) {
+            *c = b'#';
         }
     }
+
+    let (start, goal) = (Point::new(0, 0), Point::new(dim, dim));
+    let open = |q: &Point| grid.get(*q) == Some(&b'.');
+    if !open(&start) {
+        return None;
+    }
+    let next = |&p: &Point| grid.neighbors4(p).filter(open).collect::<Vec<_>>();
+    let s = search::bfs([start], next, |&p| p == goal);
+    s.dist(&goal)
 }
 
 #[cfg(test)]
@@ -133,6 +109,9 @@ mod test {
         .trim();
 
         assert_eq!(star1(sample, 6, 12), 22);
-        assert_eq!(star2(sample, 6, true), Some((6, 1)));
+        assert_eq!(star2(sample, 6, &Context::default()), Some((6, 1)));
+
+        // A byte on the start blocks the exit too.
+        assert_eq!(star2("0,0", 6, &Context::default()), Some((0, 0)));
     }
 }
@@ -1,27 +1,52 @@
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::solution::Solution;
 use anyhow::{anyhow, Result};
 use std::collections::{BTreeMap, HashSet};
+use std::fmt::Display;
 
-pub fn run(input: &str) -> Result<String> {
-    let (s1, s2) = stars(input)?;
-    Ok(format!("{s1} {s2}"))
-}
+pub struct Day;
 
-type LenToPats<'a> = BTreeMap<usize, HashSet<&'a str>>;
+impl Solution for Day {
+    type Input<'a> = (LenToPats<'a>, Vec<&'a str>);
 
-fn stars(input: &str) -> Result<(usize, usize)> {
-    let mut it = input.lines();
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        let mut it = input.lines();
+        let ltp = len_to_pats(it.next().ok_or_else(|| anyhow!("invalid input"))?);
+        it.next().ok_or_else(|| anyhow!("invalid input"))?;
+        Ok((ltp, it.collect()))
+    }
 
-    let ltp = len_to_pats(it.next().ok_or_else(|| anyhow!("invalid input"))?);
+    fn part1((ltp, designs): &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(designs
+            .iter()
+            .filter(|design| arrangements(design, ltp) > 0)
+            .count())
+    }
 
-    it.next().ok_or_else(|| anyhow!("invalid input"))?;
+    fn part2((ltp, designs): &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(designs
+            .iter()
+            .map(|design| arrangements(design, ltp))
+            .sum::<usize>())
+    }
 
-    Ok(it.fold((0, 0), |(nok, narr), design| {
-        let n = arrangements(design, &ltp);
+    // Both parts count the arrangements of every design.
+    fn both(p: &Self::Input<'_>, _: &Context) -> Answer {
+        Answer::joint(|| stars(p))
+    }
+}
+
+type LenToPats<'a> = BTreeMap<usize, HashSet<&'a str>>;
+
+fn stars((ltp, designs): &(LenToPats, Vec<&str>)) -> (usize, usize) {
+    designs.iter().fold((0, 0), |(nok, narr), design| {
+        let n = arrangements(design, ltp);
         (nok + (n > 0) as usize, narr + n)
-    }))
+    })
 }
 
-fn len_to_pats(line: &str) -> LenToPats {
+fn len_to_pats(line: &str) -> LenToPats<'_> {
     line.split(", ").fold(LenToPats::new(), |mut m, pat| {
         m.entry(pat.len())
             .and_modify(|s| {
@@ -82,6 +107,6 @@ bbrgwb
         assert_eq!(arrangements("ubwu", &ltp), 0);
         assert_eq!(arrangements("bbrgwb", &ltp), 0);
 
-        assert_eq!(stars(sample).unwrap(), (6, 16));
+        assert_eq!(stars(&Day::parse(sample, &Context::default()).unwrap()), (6, 16));
     }
 }
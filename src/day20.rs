@@ -1,37 +1,45 @@
-use crate::grid::{Grid, Point, STEPS};
+use crate::context::Context;
+use crate::grid::{Grid, Point, Tile, STEPS};
+use crate::search;
+use crate::solution::Solution;
 use anyhow::{anyhow, Result};
-use std::collections::{HashMap, VecDeque};
-
-pub fn run(input: &str) -> Result<String> {
-    let track = Track::parse(input)?;
-    let path = find_path(&track);
-    let s1 = find_cheats(track.grid.dimensions(), &path)
-        .values()
-        .filter(|&n| *n >= 100)
-        .count();
-    let s2 = find_cheats_new(&path, 20, 100, |d| d >= 100);
-    Ok(format!("{s1} {s2}"))
+use std::collections::HashMap;
+use std::fmt::Display;
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = (Track, Vec<Point>);
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        let track = Track::parse(input)?;
+        let path = find_path(&track);
+        Ok((track, path))
+    }
+
+    fn part1((track, path): &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(find_cheats(track.grid.dimensions(), path)
+            .values()
+            .filter(|&n| *n >= 100)
+            .count())
+    }
+
+    fn part2((_, path): &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(find_cheats_new(path, 20, 100, |d| d >= 100))
+    }
 }
 
-struct Track {
-    grid: Grid<u8>,
+pub struct Track {
+    grid: Grid<Tile>,
     start: Point,
     end: Point,
 }
 
 impl Track {
     fn parse(input: &str) -> Result<Self> {
-        let grid = Grid::parse(input)?;
-        let start = grid
-            .iter()
-            .find(|(_, c)| *c == &b'S')
-            .ok_or_else(|| anyhow!("no start"))?
-            .0;
-        let end = grid
-            .iter()
-            .find(|(_, c)| *c == &b'E')
-            .ok_or_else(|| anyhow!("no end"))?
-            .0;
+        let grid: Grid<Tile> = input.parse()?;
+        let start = grid.find(&Tile::Start).ok_or_else(|| anyhow!("no start"))?;
+        let end = grid.find(&Tile::End).ok_or_else(|| anyhow!("no end"))?;
         Ok(Self { grid, start, end })
     }
 }
@@ -39,44 +47,10 @@ impl Track {
 const NEW: u16 = u16::MAX;
 
 fn find_path(t: &Track) -> Vec<Point> {
-    let mut vis = Grid::new(t.grid.dimensions(), NEW);
-    *vis.get_mut(t.start).unwrap() = 0;
-    let mut work = VecDeque::from([(t.start, 0)]);
-
-    // fill vis with distance from start
-    'fill: while let Some((p, pd)) = work.pop_front() {
-        for &step in STEPS {
-            let q = p + step;
-            if let Some(qd) = vis.get_mut(q) {
-                if *qd == NEW && t.grid.get(q) != Some(&b'#') {
-                    *qd = pd + 1;
-                    work.push_back((q, *qd));
-                }
-            }
-            if q == t.end {
-                break 'fill;
-            }
-        }
-    }
-
-    // mark path by backtracking from end
-    let mut path = vec![t.end];
-    while let Some(&p) = path.last() {
-        let pd = *vis.get(p).unwrap();
-        if pd == 0 || pd == NEW {
-            break;
-        }
-        for &step in STEPS {
-            let q = p + step;
-            if let Some(qd) = vis.get(q) {
-                if *qd < pd {
-                    path.push(q);
-                }
-            }
-        }
-    }
-    path.reverse();
-    path
+    let open = |q: &Point| t.grid.get(*q).is_some_and(|&x| x != Tile::Wall);
+    let next = |&p: &Point| t.grid.neighbors4(p).filter(open).collect::<Vec<_>>();
+    let s = search::bfs([t.start], next, |&p| p == t.end);
+    s.path_to(&t.end).unwrap_or_default()
 }
 
 fn find_cheats_new(
@@ -1,10 +1,26 @@
+use crate::context::Context;
+use crate::solution::Solution;
+use anyhow::Result;
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt::Display;
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let s1 = star(1, input);
-    let s2 = star(2, input);
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = &'a str;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star(1, input))
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star(2, input))
+    }
 }
 
 fn star(num: usize, input: &str) -> usize {
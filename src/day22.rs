@@ -1,13 +1,28 @@
+use crate::context::Context;
+use crate::solution::Solution;
+use anyhow::Result;
 use std::collections::HashMap;
+use std::fmt::Display;
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let secrets = parse_input(input)?;
-    let s1 = star1(&secrets);
-    let s2 = star2(&secrets);
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Vec<u32>;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        parse_input(input)
+    }
+
+    fn part1(secrets: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star1(secrets))
+    }
+
+    fn part2(secrets: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star2(secrets))
+    }
 }
 
-fn parse_input(input: &str) -> anyhow::Result<Vec<u32>> {
+fn parse_input(input: &str) -> Result<Vec<u32>> {
     input
         .lines()
         .map(|line| line.parse::<u32>().map_err(|e| e.into()))
@@ -105,9 +120,9 @@ mod test {
     #[test]
     fn it_works() {
         assert_eq!(step_secret(123), 15887950);
-        assert_eq!(secret_iter(1).skip(SECRETS_PER_DAY).next(), Some(8685429));
+        assert_eq!(secret_iter(1).nth(SECRETS_PER_DAY), Some(8685429));
         assert_eq!(
-            price_changes_iter(123).skip(4).next(),
+            price_changes_iter(123).nth(4),
             Some((Changes([-3, 6, -1, -1]), 4))
         );
 
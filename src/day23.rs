@@ -1,12 +1,25 @@
-use anyhow::anyhow;
+use crate::context::Context;
+use crate::solution::Solution;
+use anyhow::{anyhow, Result};
 use std::collections::{BTreeSet, HashMap, HashSet};
-use std::fmt;
+use std::fmt::{self, Display};
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let network = parse_network(input)?;
-    let s1 = star1(&network);
-    let s2 = star2(&network);
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Network;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        parse_network(input)
+    }
+
+    fn part1(network: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star1(network))
+    }
+
+    fn part2(network: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star2(network))
+    }
 }
 
 fn star1(network: &Network) -> usize {
@@ -85,7 +98,7 @@ fn try_expand<'a>(network: &'a Network, set: &'a CompSet) -> impl Iterator<Item
 }
 
 #[derive(Debug, Clone)]
-struct Network(HashMap<Comp, BTreeSet<Comp>>);
+pub struct Network(HashMap<Comp, BTreeSet<Comp>>);
 
 impl Network {
     fn new() -> Self {
@@ -120,7 +133,7 @@ impl Network {
     }
 }
 
-fn parse_network(input: &str) -> anyhow::Result<Network> {
+fn parse_network(input: &str) -> Result<Network> {
     input
         .lines()
         .map(|line| parse_link(line).ok_or_else(|| anyhow!("invalid line: {line}")))
@@ -1,14 +1,26 @@
-use crate::Cli;
-use anyhow::{anyhow, bail};
+use crate::context::Context;
+use crate::solution::Solution;
+use anyhow::{anyhow, bail, Result};
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
-use std::fmt;
+use std::fmt::{self, Display};
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let p = Problem::parse(input)?;
-    let s1 = star1(&p);
-    let s2 = analyze_adder(&p.circuit, Cli::global().verbose)?;
-    Ok(format!("{s1} {s2}"))
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Problem;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        Problem::parse(input)
+    }
+
+    fn part1(p: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star1(p))
+    }
+
+    fn part2(p: &Self::Input<'_>, ctx: &Context) -> Result<impl Display> {
+        analyze_adder(&p.circuit, ctx)
+    }
 }
 
 fn star1(p: &Problem) -> u64 {
@@ -16,10 +28,10 @@ fn star1(p: &Problem) -> u64 {
     p.circuit.output(p.x, p.y, nbits).unwrap_or(0)
 }
 
-fn analyze_adder(c: &Circuit, verbose: bool) -> anyhow::Result<String> {
+fn analyze_adder(c: &Circuit, ctx: &Context) -> Result<String> {
     use Op::*;
 
-    let mut aa = AddAnalyzer::new(c, verbose);
+    let mut aa = AddAnalyzer::new(c, ctx);
     let nbits = c.output_bits();
 
     let Some(s0) = aa.find_gate(Xor, Wire::x(0), Wire::y(0)) else {
@@ -28,10 +40,8 @@ fn analyze_adder(c: &Circuit, verbose: bool) -> anyhow::Result<String> {
     let Some(mut carry) = aa.find_gate(And, Wire::x(0), Wire::y(0)) else {
         bail!("bit 00: c0 not found");
     };
-    if verbose {
-        println!(" i  s0_i  c0_i  c1_i   s_i   c_i");
-        println!("00                     {}   {}", s0, carry);
-    }
+    crate::log!(ctx, Debug, " i  s0_i  c0_i  c1_i   s_i   c_i");
+    crate::log!(ctx, Debug, "00                     {}   {}", s0, carry);
 
     for i in 1..nbits - 1 {
         let FullAdder { s0, c0, c1, si, ci } = match aa.find_full_adder(i, carry) {
@@ -40,30 +50,28 @@ fn analyze_adder(c: &Circuit, verbose: bool) -> anyhow::Result<String> {
         };
         carry = ci;
 
-        if verbose {
-            println!("{:02}   {}   {}   {}   {}   {}", i, s0, c0, c1, si, ci);
-        }
+        crate::log!(ctx, Debug, "{:02}   {}   {}   {}   {}   {}", i, s0, c0, c1, si, ci);
     }
 
     Ok(aa.into_swaps())
 }
 
-struct AddAnalyzer {
+struct AddAnalyzer<'a> {
     wg: HashMap<Wire, Gate>,
     gw: HashMap<Gate, Wire>,
     swaps: Vec<Wire>,
-    verbose: bool,
+    ctx: &'a Context,
 }
 
-impl AddAnalyzer {
-    fn new(c: &Circuit, verbose: bool) -> Self {
+impl<'a> AddAnalyzer<'a> {
+    fn new(c: &Circuit, ctx: &'a Context) -> Self {
         let wg = c.0.clone();
         let gw = wg.iter().map(|(&wire, &gate)| (gate, wire)).collect();
         Self {
             wg,
             gw,
             swaps: vec![],
-            verbose,
+            ctx,
         }
     }
 
@@ -160,9 +168,7 @@ impl AddAnalyzer {
             self.gw.insert(ag, b);
             self.gw.insert(bg, a);
 
-            if self.verbose {
-                println!("  swap {} <-> {}", a, b);
-            }
+            crate::log!(self.ctx, Debug, "  swap {} <-> {}", a, b);
             self.swaps.push(a);
             self.swaps.push(b);
             true
@@ -379,7 +385,7 @@ impl ScoreImpl {
 struct Circuit(HashMap<Wire, Gate>);
 
 impl Circuit {
-    fn parse(input: &str) -> anyhow::Result<Self> {
+    fn parse(input: &str) -> Result<Self> {
         let mut m = HashMap::new();
         for line in input.lines() {
             let mut it = line.split_ascii_whitespace();
@@ -504,14 +510,14 @@ enum Lookup {
     Valid(bool),
 }
 
-struct Problem {
+pub struct Problem {
     x: u64,
     y: u64,
     circuit: Circuit,
 }
 
 impl Problem {
-    fn parse(input: &str) -> anyhow::Result<Problem> {
+    fn parse(input: &str) -> Result<Problem> {
         let (l, r) = input
             .split_once("\n\n")
             .ok_or_else(|| anyhow!("invalid input"))?;
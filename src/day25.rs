@@ -1,9 +1,22 @@
+use crate::context::Context;
 use crate::grid::{Grid, Point};
+use crate::solution::Solution;
+use anyhow::Result;
+use std::fmt::Display;
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let schems = parse_schems(input)?;
-    let s1 = star1(&schems);
-    Ok(format!("{s1}"))
+pub struct Day;
+
+impl Solution for Day {
+    const PARTS: usize = 1;
+    type Input<'a> = Vec<Schem>;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        parse_schems(input)
+    }
+
+    fn part1(schems: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(star1(schems))
+    }
 }
 
 fn star1(schems: &[Schem]) -> usize {
@@ -19,7 +32,7 @@ fn star1(schems: &[Schem]) -> usize {
         .sum()
 }
 
-fn parse_schems(input: &str) -> anyhow::Result<Vec<Schem>> {
+fn parse_schems(input: &str) -> Result<Vec<Schem>> {
     input
         .split("\n\n")
         .map(|s| s.trim())
@@ -36,7 +49,7 @@ enum Subj {
 const PIN_HEIGHT: u8 = 7;
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
-struct Schem {
+pub struct Schem {
     subj: Subj,
     pins: [u8; 5],
 }
@@ -50,20 +63,16 @@ impl Schem {
 impl std::str::FromStr for Schem {
     type Err = anyhow::Error;
 
-    fn from_str(s: &str) -> anyhow::Result<Self> {
+    fn from_str(s: &str) -> Result<Self> {
         let grid = Grid::parse(s)?;
-        let (dx, dy) = grid.dimensions();
         let subj = if *grid.get(Point::new(0, 0)).unwrap() == b'.' {
             Subj::Key
         } else {
             Subj::Lock
         };
-        let pins = (0..dx)
-            .map(|x| {
-                (0..dy)
-                    .filter(|y| *grid.get(Point::new(x, *y)).unwrap() == b'#')
-                    .count() as u8
-            })
+        let pins = grid
+            .columns()
+            .map(|c| c.filter(|&&v| v == b'#').count() as u8)
             .collect::<Vec<u8>>()
             .as_slice()
             .try_into()?;
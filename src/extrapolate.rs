@@ -1,6 +1,5 @@
 // Extrapolate the nth value from the values in it,
 // assuming the values in the sequence grow polynomially.
-#[allow(unused)]
 pub fn nth(mut it: impl Iterator<Item = usize>, n: usize) -> Option<usize> {
     let mut v = vec![];
 
@@ -1,6 +1,8 @@
 use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 
 // Cell x and y coordinates
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -52,14 +54,11 @@ impl std::ops::Sub for Point {
     }
 }
 
-#[allow(unused)]
 pub const STEPS: &[Point; 4] = &[pt(0, -1), pt(0, 1), pt(-1, 0), pt(1, 0)];
 
-#[allow(unused)]
 pub const DIRS: &[Dir; 4] = &[Dir::North, Dir::South, Dir::West, Dir::East];
 
 // Cardinal directions
-#[allow(unused)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum Dir {
     North,
@@ -68,7 +67,6 @@ pub enum Dir {
     West,
 }
 
-#[allow(unused)]
 impl Dir {
     pub fn from_xy(p: Point) -> Option<Self> {
         let (dx, dy) = p.xy();
@@ -136,15 +134,159 @@ impl Dir {
     }
 }
 
-#[derive(Clone, Eq, PartialEq)]
+pub const DIRS8: &[Dir8; 8] = &[
+    Dir8::North,
+    Dir8::NorthEast,
+    Dir8::East,
+    Dir8::SouthEast,
+    Dir8::South,
+    Dir8::SouthWest,
+    Dir8::West,
+    Dir8::NorthWest,
+];
+
+// Cardinal and diagonal directions, clockwise from north as in DIRS8
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Dir8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Dir8 {
+    pub fn from_xy(p: Point) -> Option<Self> {
+        let (dx, dy) = p.xy();
+        if (dx, dy) == (0, 0) || (dx != 0 && dy != 0 && dx.abs() != dy.abs()) {
+            return None;
+        }
+        DIRS8
+            .iter()
+            .copied()
+            .find(|d| d.step(1) == pt(dx.signum(), dy.signum()))
+    }
+
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    // Direction n eighths of a turn clockwise, counterclockwise if n is negative.
+    pub fn turn(self, n: i32) -> Self {
+        DIRS8[(self.index() as i32 + n).rem_euclid(8) as usize]
+    }
+
+    pub fn left45(self) -> Self {
+        self.turn(-1)
+    }
+
+    pub fn right45(self) -> Self {
+        self.turn(1)
+    }
+
+    pub fn left(self) -> Self {
+        self.turn(-2)
+    }
+
+    pub fn right(self) -> Self {
+        self.turn(2)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    pub fn step(self, x: i32) -> Point {
+        match self {
+            Self::North => pt(0, -x),
+            Self::NorthEast => pt(x, -x),
+            Self::East => pt(x, 0),
+            Self::SouthEast => pt(x, x),
+            Self::South => pt(0, x),
+            Self::SouthWest => pt(-x, x),
+            Self::West => pt(-x, 0),
+            Self::NorthWest => pt(-x, -x),
+        }
+    }
+}
+
+impl From<Dir> for Dir8 {
+    fn from(d: Dir) -> Self {
+        match d {
+            Dir::North => Self::North,
+            Dir::South => Self::South,
+            Dir::East => Self::East,
+            Dir::West => Self::West,
+        }
+    }
+}
+
+impl TryFrom<Dir8> for Dir {
+    type Error = anyhow::Error;
+
+    fn try_from(d: Dir8) -> Result<Self> {
+        match d {
+            Dir8::North => Ok(Self::North),
+            Dir8::South => Ok(Self::South),
+            Dir8::East => Ok(Self::East),
+            Dir8::West => Ok(Self::West),
+            _ => bail!("{:?} is not a cardinal direction", d),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Grid<T> {
     dx: i32,
     dy: i32,
     m: Vec<T>,
 }
 
-#[allow(unused)]
 impl<T> Grid<T> {
+    // Parses input with one line per row, converting each char to a cell with f.
+    // Errors tell the line and column of the offending cell, counting from 1.
+    pub fn parse_with<F>(input: &str, mut f: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Result<T>,
+    {
+        let mut m = Vec::new();
+        let (mut dx, mut dy) = (0, 0);
+        for (y, line) in input.lines().enumerate() {
+            let n = m.len();
+            for (x, c) in line.chars().enumerate() {
+                let v = f(c).map_err(|e| {
+                    anyhow!(
+                        "line {}, column {}: invalid cell {:?}: {}",
+                        y + 1,
+                        x + 1,
+                        c,
+                        e
+                    )
+                })?;
+                m.push(v);
+            }
+            let w = m.len() - n;
+            if y == 0 {
+                dx = w;
+            } else if w != dx {
+                bail!("line {}: {} cells, expected {}", y + 1, w, dx);
+            }
+            dy += 1;
+        }
+        Ok(Self {
+            dx: dx as i32,
+            dy,
+            m,
+        })
+    }
+
     pub fn positions(&self) -> impl Iterator<Item = Point> + '_ {
         (0..self.dy).flat_map(|y| (0..self.dx).map(move |x| pt(x, y)))
     }
@@ -165,6 +307,30 @@ impl<T> Grid<T> {
         self.m.chunks_mut(self.dx as usize)
     }
 
+    // Cells of each column, from left to right and top to bottom.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (dx, dy) = (self.dx as usize, self.dy as usize);
+        (0..dx).map(move |x| (0..dy).map(move |y| &self.m[y * dx + x]))
+    }
+
+    // View of the cells of the rectangle of dimensions starting at origin,
+    // if it is inside the grid.
+    pub fn view(&self, origin: Point, (dx, dy): (i32, i32)) -> Option<GridView<'_, T>> {
+        let end = origin + pt(dx, dy);
+        let inside = dx >= 0
+            && dy >= 0
+            && origin.x >= 0
+            && origin.y >= 0
+            && end.x <= self.dx
+            && end.y <= self.dy;
+        inside.then_some(GridView {
+            grid: self,
+            origin,
+            dx,
+            dy,
+        })
+    }
+
     pub fn as_slice(&self) -> &[T] {
         &self.m
     }
@@ -192,6 +358,22 @@ impl<T> Grid<T> {
         px >= 0 && px < self.dx && py >= 0 && py < self.dy
     }
 
+    // Points next to p in the four directions of STEPS, inside the grid.
+    pub fn neighbors4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        STEPS
+            .iter()
+            .map(move |&s| p + s)
+            .filter(|&q| self.is_inside(q))
+    }
+
+    // Points next to p in the eight directions of DIRS8, inside the grid.
+    pub fn neighbors8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        DIRS8
+            .iter()
+            .map(move |d| p + d.step(1))
+            .filter(|&q| self.is_inside(q))
+    }
+
     pub fn get(&self, p: Point) -> Option<&T> {
         self.to_index(p).map(|i| &self.m[i])
     }
@@ -218,7 +400,6 @@ impl<T> Grid<T> {
     }
 }
 
-#[allow(unused)]
 impl<T: Clone> Grid<T> {
     pub fn new((dx, dy): (i32, i32), v: T) -> Self {
         Self {
@@ -228,6 +409,47 @@ impl<T: Clone> Grid<T> {
         }
     }
 
+    // New grid of dimensions, with cells copied from the positions given by src.
+    fn remap<F>(&self, (dx, dy): (i32, i32), src: F) -> Self
+    where
+        F: Fn(Point) -> Point,
+    {
+        let m = (0..dy)
+            .flat_map(|y| (0..dx).map(move |x| pt(x, y)))
+            .map(|p| self.get(src(p)).unwrap().clone())
+            .collect();
+        Self { dx, dy, m }
+    }
+
+    // Rows become columns: the cell at (x, y) moves to (y, x).
+    pub fn transpose(&self) -> Self {
+        self.remap((self.dy, self.dx), |p| pt(p.y, p.x))
+    }
+
+    // Rotates a quarter turn clockwise, the left column becomes the top row.
+    pub fn rotate_cw(&self) -> Self {
+        let dy = self.dy;
+        self.remap((self.dy, self.dx), |p| pt(p.y, dy - 1 - p.x))
+    }
+
+    // Rotates a quarter turn counterclockwise, the top row becomes the left column.
+    pub fn rotate_ccw(&self) -> Self {
+        let dx = self.dx;
+        self.remap((self.dy, self.dx), |p| pt(dx - 1 - p.y, p.x))
+    }
+
+    // Mirrors left and right.
+    pub fn flip_h(&self) -> Self {
+        let dx = self.dx;
+        self.remap(self.dimensions(), |p| pt(dx - 1 - p.x, p.y))
+    }
+
+    // Mirrors top and bottom.
+    pub fn flip_v(&self) -> Self {
+        let dy = self.dy;
+        self.remap(self.dimensions(), |p| pt(p.x, dy - 1 - p.y))
+    }
+
     pub fn fill(&mut self, fillc: T) {
         self.m.fill(fillc)
     }
@@ -247,7 +469,6 @@ impl<T: Clone> Grid<T> {
     }
 }
 
-#[allow(unused)]
 impl Grid<u8> {
     pub fn parse(input: &str) -> Result<Self> {
         let (dx, m) = input
@@ -275,7 +496,139 @@ impl Grid<u8> {
     }
 }
 
-#[allow(unused)]
+// Rectangular part of a grid, borrowed from it.
+// Points are relative to the top left corner of the view.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Point,
+    dx: i32,
+    dy: i32,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn dimensions(&self) -> (i32, i32) {
+        (self.dx, self.dy)
+    }
+
+    // Position of the top left corner in the grid.
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    pub fn is_inside(&self, p: Point) -> bool {
+        let (px, py) = p.xy();
+        px >= 0 && px < self.dx && py >= 0 && py < self.dy
+    }
+
+    pub fn get(&self, p: Point) -> Option<&'a T> {
+        if !self.is_inside(p) {
+            return None;
+        }
+        self.grid.get(self.origin + p)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let (dx, dy) = (self.dx, self.dy);
+        (0..dy).flat_map(move |y| (0..dx).map(move |x| pt(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &'a T)> + '_ {
+        self.positions().map(|p| (p, self.get(p).unwrap()))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let (grid, origin, dx) = (self.grid, self.origin, self.dx as usize);
+        (origin.y..origin.y + self.dy).map(move |y| {
+            let start = (origin.x + y * grid.dx) as usize;
+            &grid.m[start..start + dx]
+        })
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &'a T>> {
+        let v = *self;
+        (0..v.dx).map(move |x| (0..v.dy).map(move |y| v.get(pt(x, y)).unwrap()))
+    }
+
+    // View of a part of this view, with points relative to this view.
+    pub fn view(&self, origin: Point, dims: (i32, i32)) -> Option<Self> {
+        let end = origin + pt(dims.0, dims.1);
+        if origin.x < 0 || origin.y < 0 || end.x > self.dx || end.y > self.dy {
+            return None;
+        }
+        self.grid.view(self.origin + origin, dims)
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    // Copies the cells of the view into a grid of their own.
+    pub fn to_grid(&self) -> Grid<T> {
+        let m = self.rows().flat_map(|r| r.iter().cloned()).collect();
+        Grid {
+            dx: self.dx,
+            dy: self.dy,
+            m,
+        }
+    }
+}
+
+// Parses cells that convert from chars, such as enums of the puzzle tiles.
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+    T::Error: fmt::Display,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_with(s, |c| T::try_from(c).map_err(|e| anyhow!("{}", e)))
+    }
+}
+
+// One line per row, with the cells side by side.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for v in row {
+                write!(f, "{}", v)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// Cell of a maze with walls, a start and an end, as in `#.SE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Wall,
+    Open,
+    Start,
+    End,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        Ok(match c {
+            '#' => Tile::Wall,
+            '.' => Tile::Open,
+            'S' => Tile::Start,
+            'E' => Tile::End,
+            _ => bail!("unknown tile"),
+        })
+    }
+}
+
 impl<T: PartialEq> Grid<T> {
     pub fn find(&self, what: &T) -> Option<Point> {
         self.m
@@ -285,7 +638,6 @@ impl<T: PartialEq> Grid<T> {
     }
 }
 
-#[allow(unused)]
 impl<T: PartialEq + Clone> Grid<T> {
     pub fn flood<P>(&mut self, start: Point, value: T, mut pred: P)
     where
@@ -309,3 +661,272 @@ impl<T: PartialEq + Clone> Grid<T> {
         }
     }
 }
+
+// Connected region of a grid, as found by Grid::regions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    // First cell of the region, in the order of Grid::positions.
+    pub start: Point,
+    pub area: usize,
+    // Cell edges between the region and the rest of the grid or its outside.
+    pub perimeter: usize,
+    // Straight runs of those edges, that is the number of corners.
+    pub sides: usize,
+    // Top left corner and dimensions of the bounding box, as for Grid::view.
+    pub origin: Point,
+    pub dims: (i32, i32),
+    // Parts of the grid enclosed by the region, connected through the
+    // four directions like the regions themselves.
+    pub holes: usize,
+}
+
+// Regions of a grid, with the index in regions of the region of each cell.
+#[derive(Debug, Clone)]
+pub struct Regions {
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl<T: PartialEq> Grid<T> {
+    // Regions of cells with equal values.
+    pub fn regions(&self) -> Regions {
+        self.regions_by(|a, b| a == b)
+    }
+}
+
+impl<T> Grid<T> {
+    // Regions of cells connected through the four directions,
+    // where same tells whether two neighbor cells are in the same region.
+    pub fn regions_by<F>(&self, mut same: F) -> Regions
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        const NONE: usize = usize::MAX;
+        let mut labels = Grid::new(self.dimensions(), NONE);
+        let mut regions = vec![];
+        for start in self.positions() {
+            if labels.get(start) != Some(&NONE) {
+                continue;
+            }
+            let label = regions.len();
+            let (mut min, mut max) = (start, start);
+            let mut area = 0;
+            *labels.get_mut(start).unwrap() = label;
+            let mut stack = vec![start];
+            while let Some(p) = stack.pop() {
+                area += 1;
+                min = pt(min.x.min(p.x), min.y.min(p.y));
+                max = pt(max.x.max(p.x), max.y.max(p.y));
+                let v = self.get(p).unwrap();
+                for q in self.neighbors4(p) {
+                    if labels.get(q) == Some(&NONE) && same(v, self.get(q).unwrap()) {
+                        *labels.get_mut(q).unwrap() = label;
+                        stack.push(q);
+                    }
+                }
+            }
+            regions.push(Region {
+                start,
+                area,
+                perimeter: 0,
+                sides: 0,
+                origin: min,
+                dims: (max.x - min.x + 1, max.y - min.y + 1),
+                holes: 0,
+            });
+        }
+
+        // Corners of a cell, seen from the cell, with the two sides meeting there.
+        // Outer corners have no neighbor on either side, inner ones have both
+        // but not the diagonal neighbor between them.
+        for (p, &l) in labels.iter() {
+            let inside = |d: Point| labels.get(p + d) == Some(&l);
+            let r = &mut regions[l];
+            r.perimeter += STEPS.iter().filter(|&&s| !inside(s)).count();
+            for d in DIRS {
+                let (a, b) = (d.step(1), d.right().step(1));
+                match (inside(a), inside(b), inside(a + b)) {
+                    (false, false, _) | (true, true, false) => r.sides += 1,
+                    _ => {}
+                }
+            }
+        }
+        for (l, r) in regions.iter_mut().enumerate() {
+            r.holes = count_holes(&labels, l, r.origin, r.dims);
+        }
+        Regions { labels, regions }
+    }
+}
+
+// Number of parts of the bounding box at origin with dims, connected through
+// the four directions, that have no cell labeled l and don't touch the box edge.
+fn count_holes(labels: &Grid<usize>, l: usize, origin: Point, dims: (i32, i32)) -> usize {
+    let mut seen = Grid::new(dims, false);
+    let open = |p: Point| labels.get(origin + p) != Some(&l);
+    let on_edge = |p: Point| p.x == 0 || p.y == 0 || p.x == dims.0 - 1 || p.y == dims.1 - 1;
+    let mut holes = 0;
+    let cells: Vec<Point> = seen.positions().collect();
+    for start in cells {
+        if seen.get(start) != Some(&false) || !open(start) {
+            continue;
+        }
+        *seen.get_mut(start).unwrap() = true;
+        let mut stack = vec![start];
+        let mut enclosed = true;
+        while let Some(p) = stack.pop() {
+            enclosed &= !on_edge(p);
+            for &d in STEPS {
+                let q = p + d;
+                if seen.get(q) == Some(&false) && open(q) {
+                    *seen.get_mut(q).unwrap() = true;
+                    stack.push(q);
+                }
+            }
+        }
+        if enclosed {
+            holes += 1;
+        }
+    }
+    holes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Tile {
+        Open,
+        Wall,
+    }
+
+    impl TryFrom<char> for Tile {
+        type Error = anyhow::Error;
+
+        fn try_from(c: char) -> Result<Self> {
+            match c {
+                '.' => Ok(Tile::Open),
+                '#' => Ok(Tile::Wall),
+                _ => bail!("not a tile"),
+            }
+        }
+    }
+
+    impl fmt::Display for Tile {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(if *self == Tile::Wall { "#" } else { "." })
+        }
+    }
+
+    #[test]
+    fn it_works() {
+        let g: Grid<Tile> = "#.#\n..#\n".parse().unwrap();
+        assert_eq!(g.dimensions(), (3, 2));
+        assert_eq!(g.get(pt(1, 1)), Some(&Tile::Open));
+        assert_eq!(g.to_string(), "#.#\n..#\n");
+
+        let e = "#.#\n.x#\n".parse::<Grid<Tile>>().unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 2: invalid cell 'x': not a tile"
+        );
+        let e = "#.#\n.#\n".parse::<Grid<Tile>>().unwrap_err();
+        assert_eq!(e.to_string(), "line 2: 2 cells, expected 3");
+
+        let g = Grid::parse_with("12\n34", |c| {
+            c.to_digit(10).ok_or_else(|| anyhow!("not a digit"))
+        })
+        .unwrap();
+        assert_eq!(g.to_string(), "12\n34\n");
+        assert_eq!(
+            "ab".parse::<Grid<char>>().unwrap().find(&'b'),
+            Some(pt(1, 0))
+        );
+        assert_eq!("".parse::<Grid<char>>().unwrap().dimensions(), (0, 0));
+    }
+
+    #[test]
+    fn transform_works() {
+        let g: Grid<char> = "abc\ndef\n".parse().unwrap();
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(g.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(g.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(g.flip_h().to_string(), "cba\nfed\n");
+        assert_eq!(g.flip_v().to_string(), "def\nabc\n");
+        assert_eq!(g.rotate_cw().rotate_cw(), g.flip_h().flip_v());
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+
+        let cols: Vec<String> = g.columns().map(|c| c.collect()).collect();
+        assert_eq!(cols, ["ad", "be", "cf"]);
+        let empty = Grid::new((3, 0), 0);
+        assert!(empty.columns().all(|mut c| c.next().is_none()));
+
+        let v = g.view(pt(1, 0), (2, 2)).unwrap();
+        assert_eq!(v.get(pt(0, 0)), Some(&'b'));
+        assert_eq!(v.get(pt(1, 1)), Some(&'f'));
+        assert_eq!(v.get(pt(2, 0)), None);
+        assert_eq!(v.to_grid().to_string(), "bc\nef\n");
+        let cols: Vec<String> = v.columns().map(|c| c.collect()).collect();
+        assert_eq!(cols, ["be", "cf"]);
+        let w = v.view(pt(1, 1), (1, 1)).unwrap();
+        assert_eq!(w.origin(), pt(2, 1));
+        assert_eq!(w.iter().collect::<Vec<_>>(), [(pt(0, 0), &'f')]);
+        assert!(v.view(pt(1, 1), (2, 1)).is_none());
+        assert!(g.view(pt(2, 0), (2, 1)).is_none());
+    }
+
+    #[test]
+    fn regions_work() {
+        let g: Grid<char> = "AAAA\nA.BA\nAAAA\nCC.C\n".parse().unwrap();
+        let r = g.regions();
+        assert_eq!(r.regions.len(), 6);
+        assert_eq!(r.labels.get(pt(3, 1)), Some(&0));
+        let a = &r.regions[0];
+        assert_eq!((a.area, a.perimeter, a.sides, a.holes), (10, 20, 8, 1));
+        assert_eq!((a.origin, a.dims), (pt(0, 0), (4, 3)));
+        let b = &r.regions[*r.labels.get(pt(2, 1)).unwrap()];
+        assert_eq!((b.start, b.area, b.perimeter, b.sides), (pt(2, 1), 1, 4, 4));
+        assert_eq!(r.regions[*r.labels.get(pt(3, 3)).unwrap()].start, pt(3, 3));
+
+        // With all the letters together, only the dot inside is enclosed.
+        let r = g.regions_by(|a, b| a.is_alphabetic() == b.is_alphabetic());
+        assert_eq!(r.regions.len(), 3);
+        assert_eq!((r.regions[0].area, r.regions[0].holes), (14, 1));
+
+        // Holes touching diagonally are separate, and so is a hole touching
+        // the outside only diagonally.
+        let g: Grid<char> = "AAA\nA.A\nAA.\n".parse().unwrap();
+        let a = &g.regions().regions[0];
+        assert_eq!((a.area, a.sides, a.holes), (7, 10, 1));
+        let g: Grid<char> = "AAAA\nA.AA\nAA.A\nAAAA\n".parse().unwrap();
+        assert_eq!(g.regions().regions[0].holes, 2);
+        let g: Grid<char> = "AAAAA\nA.A.A\nAAAAA\n".parse().unwrap();
+        assert_eq!(g.regions().regions[0].holes, 2);
+    }
+
+    #[test]
+    fn dir8_works() {
+        assert_eq!(Dir8::North.right45(), Dir8::NorthEast);
+        assert_eq!(Dir8::North.left45(), Dir8::NorthWest);
+        assert_eq!(Dir8::NorthWest.right(), Dir8::NorthEast);
+        assert_eq!(Dir8::SouthWest.opposite(), Dir8::NorthEast);
+        assert_eq!(Dir8::East.turn(-10), Dir8::North);
+        for &d in DIRS8 {
+            assert_eq!(Dir8::from_xy(d.step(3)), Some(d));
+            assert_eq!(d.is_diagonal(), Dir::try_from(d).is_err());
+        }
+        assert_eq!(Dir8::from_xy(pt(2, 1)), None);
+        for &d in DIRS {
+            assert_eq!(Dir8::from(d).step(2), d.step(2));
+            assert_eq!(Dir::try_from(Dir8::from(d)).unwrap(), d);
+            assert_eq!(Dir8::from(d.right()), Dir8::from(d).right());
+        }
+
+        let g = Grid::new((3, 2), 0);
+        let v: Vec<_> = g.neighbors4(pt(0, 0)).collect();
+        assert_eq!(v, [pt(0, 1), pt(1, 0)]);
+        assert_eq!(g.neighbors4(pt(1, 0)).count(), 3);
+        assert_eq!(g.neighbors8(pt(1, 0)).count(), 5);
+        assert_eq!(g.neighbors8(pt(2, 1)).count(), 3);
+    }
+}
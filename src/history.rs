@@ -0,0 +1,263 @@
+use crate::bench::Stats;
+use crate::select::DayList;
+use anyhow::{anyhow, Context, Result};
+use aoc24::fmt_duration;
+use aoc24::input::InputSource;
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Args)]
+pub struct HistoryArgs {
+    /// Number of earlier runs on the same input forming the baseline
+    #[arg(short, long, default_value_t = 5)]
+    window: usize,
+
+    /// Slowdown over the baseline, in percent, that is reported as a regression
+    #[arg(short, long, value_name = "PCT", default_value_t = 20.0)]
+    threshold: f64,
+
+    /// Days to report, such as `1-5,9`; all days with history if omitted
+    #[arg(value_name = "DAYS")]
+    days: Vec<DayList>,
+}
+
+// Timing of one day in one run of the binary.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub time: u64,
+    pub year: u32,
+    pub day: usize,
+    pub input: String,
+    pub rev: Option<String>,
+    pub parse_ms: f64,
+    pub solve_ms: f64,
+}
+
+impl Entry {
+    pub fn new(year: u32, day: usize, input: String, parse: Duration, solve: Duration) -> Self {
+        let time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self {
+            time,
+            year,
+            day,
+            input,
+            rev: None,
+            parse_ms: parse.as_secs_f64() * 1000.0,
+            solve_ms: solve.as_secs_f64() * 1000.0,
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        Duration::from_secs_f64((self.parse_ms + self.solve_ms) / 1000.0)
+    }
+}
+
+// Run history, stored as one JSON object per line.
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    pub fn open(is: &InputSource) -> Result<Self> {
+        let path = is
+            .history_path()
+            .ok_or_else(|| anyhow!("no cache directory"))?;
+        Ok(Self { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn load(&self) -> Result<Vec<Entry>> {
+        let s = match fs::read_to_string(&self.path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e).with_context(|| format!("reading {:?}", self.path)),
+        };
+        s.lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| {
+                serde_json::from_str(l).with_context(|| format!("{:?}:{}", self.path, i + 1))
+            })
+            .collect()
+    }
+
+    // Appends entries, stamped with the revision of the source tree.
+    pub fn append(&self, entries: &mut [Entry]) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let rev = git_rev();
+        let mut s = String::new();
+        for e in entries.iter_mut() {
+            e.rev.clone_from(&rev);
+            s.push_str(&serde_json::to_string(e)?);
+            s.push('\n');
+        }
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut f| f.write_all(s.as_bytes()))
+            .with_context(|| format!("writing {:?}", self.path))
+    }
+}
+
+// FNV-1a hash of the input, to tell runs on different inputs apart.
+pub fn input_hash(input: &str) -> String {
+    let h = input.bytes().fold(0xcbf29ce484222325u64, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", h)
+}
+
+// Revision of the source tree the binary was built from, if it is a git checkout.
+fn git_rev() -> Option<String> {
+    option_env!("GIT_REV").map(str::to_owned)
+}
+
+// Latest run of a day compared with the runs before it on the same input.
+#[derive(Debug)]
+struct Comparison<'a> {
+    latest: &'a Entry,
+    baseline: Option<Stats>,
+}
+
+impl Comparison<'_> {
+    // Change of the latest time relative to the baseline median, in percent.
+    fn change(&self) -> Option<f64> {
+        let base = self.baseline?.median.as_secs_f64();
+        (base > 0.0).then(|| (self.latest.total().as_secs_f64() / base - 1.0) * 100.0)
+    }
+}
+
+fn compare(entries: &[Entry], window: usize) -> BTreeMap<usize, Comparison<'_>> {
+    let mut by_day: BTreeMap<usize, Vec<&Entry>> = BTreeMap::new();
+    for e in entries {
+        by_day.entry(e.day).or_default().push(e);
+    }
+    by_day
+        .into_iter()
+        .map(|(day, v)| {
+            let (latest, rest) = v.split_last().unwrap();
+            let base = rest
+                .iter()
+                .rev()
+                .filter(|e| e.input == latest.input)
+                .take(window)
+                .map(|e| e.total())
+                .collect();
+            let c = Comparison {
+                latest,
+                baseline: Stats::new(base),
+            };
+            (day, c)
+        })
+        .collect()
+}
+
+pub fn main(is: &InputSource, args: &HistoryArgs) -> Result<()> {
+    let history = History::open(is)?;
+    let entries: Vec<Entry> = history
+        .load()?
+        .into_iter()
+        .filter(|e| e.year == is.year())
+        .filter(|e| args.days.is_empty() || args.days.iter().any(|l| l.contains(e.day)))
+        .collect();
+    if entries.is_empty() {
+        println!("No run history in {:?}", history.path());
+        return Ok(());
+    }
+
+    let mut nslow = 0;
+    for (day, c) in compare(&entries, args.window) {
+        let rev = c.latest.rev.as_deref().unwrap_or("-");
+        let latest = fmt_duration(c.latest.total());
+        let (Some(base), Some(change)) = (c.baseline, c.change()) else {
+            println!("Day {:2}: {:>9}  no baseline  {}", day, latest, rev);
+            continue;
+        };
+        let slow = change > args.threshold;
+        if slow {
+            nslow += 1;
+        }
+        println!(
+            "Day {:2}: {:>9}  baseline {:>9} ({} runs)  {:+7.1}%  {}{}",
+            day,
+            latest,
+            fmt_duration(base.median),
+            base.runs,
+            change,
+            rev,
+            if slow { "  SLOWER" } else { "" },
+        );
+    }
+    if nslow > 0 {
+        println!(
+            "{} day(s) slower than the baseline by more than {}%",
+            nslow, args.threshold
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(day: usize, input: &str, ms: f64) -> Entry {
+        let mut e = Entry::new(2024, day, input.into(), Duration::ZERO, Duration::ZERO);
+        e.solve_ms = ms;
+        e
+    }
+
+    #[test]
+    fn it_works() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_ne!(input_hash("1\n"), input_hash("2\n"));
+
+        let entries = vec![
+            entry(1, "a", 10.0),
+            entry(2, "a", 5.0),
+            entry(1, "a", 12.0),
+            entry(1, "b", 1.0),
+            entry(1, "a", 11.0),
+            entry(1, "a", 15.0),
+            entry(2, "b", 5.0),
+        ];
+        let m = compare(&entries, 3);
+
+        // The run on the other input is not part of the baseline.
+        let c = &m[&1];
+        assert_eq!(c.latest.solve_ms, 15.0);
+        let base = c.baseline.unwrap();
+        assert_eq!(base.runs, 3);
+        assert_eq!(base.median, Duration::from_millis(11));
+        assert!((c.change().unwrap() - 36.36).abs() < 0.01);
+
+        let c = &m[&2];
+        assert!(c.baseline.is_none());
+        assert_eq!(c.change(), None);
+
+        let dir = std::env::temp_dir().join(format!("aoc-history-{}", std::process::id()));
+        let h = History {
+            path: dir.join("history"),
+        };
+        let mut v = entries[..2].to_vec();
+        h.append(&mut v).unwrap();
+        assert_eq!(h.load().unwrap(), v);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,88 +1,348 @@
-use crate::AOC_YEAR;
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use directories::BaseDirs;
+use once_cell::sync::OnceCell;
 use reqwest::blocking::Client;
 use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::StatusCode;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
 
 pub struct InputSource {
-    client: Client,
+    year: u32,
+    base_url: String,
+    session: Option<String>,
+    client: OnceCell<Client>,
 }
 
 impl InputSource {
-    pub fn new() -> Result<InputSource> {
-        let sessionvar = format!("AOC{}_SESSION", AOC_YEAR);
-
-        let session = env::var(&sessionvar).with_context(|| {
-            format!(
-                "Environment variable {sessionvar} is unset.
-Set it to the value of the `session` cookie from the advent of code website."
-            )
-        })?;
+    pub fn new(year: u32) -> InputSource {
+        InputSource {
+            year,
+            base_url: BASE_URL.to_owned(),
+            session: None,
+            client: OnceCell::new(),
+        }
+    }
 
-        let mut headers = HeaderMap::new();
-        let ck = HeaderValue::from_str(&format!("session={}", session))?;
-        headers.insert("cookie", ck);
+    pub fn with_base_url(mut self, url: &str) -> Self {
+        self.base_url = url.trim_end_matches('/').to_owned();
+        self
+    }
 
-        let c = Client::builder().default_headers(headers).build()?;
+    // Uses session instead of the one from the environment.
+    pub fn with_session(mut self, session: &str) -> Self {
+        self.session = Some(session.to_owned());
+        self
+    }
 
-        Ok(InputSource { client: c })
+    pub fn year(&self) -> u32 {
+        self.year
     }
 
     pub fn get(&self, day: usize) -> Result<String> {
-        if let Some(s) = InputSource::get_cache(day) {
+        if let Some(s) = self.get_cache(day) {
+            if let Some(cause) = error_cause(&s) {
+                bail!(
+                    "cached input for day {day} is an error response, {cause}.
+Remove it with `cache check --evict`."
+                );
+            }
             return Ok(s);
         }
 
-        let r = self.get_https(day)?;
+        let r = self.get_https(day).with_context(|| {
+            format!("input for day {day} is not cached and could not be downloaded")
+        })?;
 
-        InputSource::put_cache(day, &r);
+        self.put_cache(day, &r);
 
         Ok(r)
     }
 
+    // The HTTP client is created on first use,
+    // so that the session cookie is needed only when something is downloaded.
+    fn client(&self) -> Result<&Client> {
+        self.client.get_or_try_init(|| {
+            // The session cookie may be set per year, or for all years.
+            let sessionvar = format!("AOC{}_SESSION", self.year);
+            let session = match &self.session {
+                Some(s) => s.clone(),
+                None => env::var(&sessionvar)
+                    .or_else(|_| env::var("AOC_SESSION"))
+                    .map_err(|_| {
+                        anyhow!(
+                            "environment variables {sessionvar} and AOC_SESSION are unset.
+Set one to the value of the `session` cookie from the advent of code website."
+                        )
+                    })?,
+            };
+
+            let mut headers = HeaderMap::new();
+            let ck = HeaderValue::from_str(&format!("session={}", session))?;
+            headers.insert("cookie", ck);
+
+            Ok(Client::builder().default_headers(headers).build()?)
+        })
+    }
+
     fn get_https(&self, day: usize) -> Result<String> {
-        let url = format!("https://adventofcode.com/{}/day/{}/input", AOC_YEAR, day);
-        Ok(self.client.get(&url[..]).send()?.text()?)
+        let url = format!("{}/{}/day/{}/input", self.base_url, self.year, day);
+        let resp = self.client()?.get(&url[..]).send()?;
+        let status = resp.status();
+        let body = resp.text()?;
+        check_response(status, &body)?;
+        Ok(body)
+    }
+
+    // Downloads the puzzle page of day, and saves it in the cache
+    // so that it can be processed again offline.
+    pub fn get_page(&self, day: usize) -> Result<String> {
+        let url = format!("{}/{}/day/{}", self.base_url, self.year, day);
+        let resp = self.client()?.get(&url[..]).send()?;
+        let status = resp.status();
+        let body = resp.text()?;
+        if !status.is_success() {
+            bail!("HTTP {status}: puzzle page of day {day} is not available");
+        }
+        if let Some(path) = self.page_path(day, "html") {
+            if let Err(err) =
+                fs::create_dir_all(path.parent().unwrap()).and_then(|_| fs::write(&path, &body))
+            {
+                eprintln!("error writing cache file {:?}: {}", path, err);
+            }
+        }
+        Ok(body)
+    }
+
+    // Posts answer for a part of day, and returns the response page.
+    pub fn post_answer(&self, day: usize, part: usize, answer: &str) -> Result<String> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, self.year, day);
+        let level = part.to_string();
+        let resp = self
+            .client()?
+            .post(&url[..])
+            .form(&[("level", &level[..]), ("answer", answer)])
+            .send()?;
+        let status = resp.status();
+        let body = resp.text()?;
+        if !status.is_success() {
+            bail!("HTTP {status}: answer was not accepted");
+        }
+        Ok(body)
+    }
+
+    // Days with cached inputs that look like error responses, with the likely cause.
+    pub fn poisoned(&self) -> Vec<(usize, &'static str)> {
+        self.cached_days()
+            .into_iter()
+            .filter_map(|day| {
+                let s = self.get_cache(day)?;
+                error_cause(&s).map(|cause| (day, cause))
+            })
+            .collect()
+    }
+
+    pub fn cached_days(&self) -> Vec<usize> {
+        let Some(entries) = self.cache_dir().and_then(|p| fs::read_dir(p).ok()) else {
+            return vec![];
+        };
+        let mut v: Vec<usize> = entries
+            .filter_map(|e| e.ok()?.file_name().to_str()?.parse().ok())
+            .collect();
+        v.sort();
+        v
+    }
+
+    pub fn evict(&self, day: usize) -> Result<()> {
+        let path = self
+            .cache_path(day)
+            .ok_or_else(|| anyhow!("no cache directory"))?;
+        fs::remove_file(&path).with_context(|| format!("removing {:?}", path))
     }
 
-    fn cache_folder() -> String {
-        format!("aoc{}", AOC_YEAR)
+    fn cache_folder(&self) -> String {
+        format!("aoc{}", self.year)
     }
 
-    fn get_cache(day: usize) -> Option<String> {
+    fn cache_dir(&self) -> Option<PathBuf> {
         let base_dirs = BaseDirs::new()?;
 
         let mut path = PathBuf::new();
         path.push(base_dirs.cache_dir());
-        path.push(Self::cache_folder());
-        path.push(day.to_string());
+        path.push(self.cache_folder());
+        Some(path)
+    }
 
-        fs::read_to_string(&path).ok()
+    // Location of the recorded answers, next to the cached inputs.
+    pub fn answers_path(&self) -> Option<PathBuf> {
+        self.cache_dir().map(|p| p.join("answers"))
     }
 
-    fn put_cache(day: usize, contents: &str) {
-        let base_dirs = match BaseDirs::new() {
-            Some(x) => x,
-            None => {
-                return;
-            }
-        };
+    // Location of files about the puzzle of day, such as its page.
+    pub fn page_path(&self, day: usize, ext: &str) -> Option<PathBuf> {
+        self.cache_dir().map(|p| p.join(format!("{}.{}", day, ext)))
+    }
 
-        let mut path = PathBuf::new();
-        path.push(base_dirs.cache_dir());
-        path.push(Self::cache_folder());
-        if !path.exists() {
-            if let Err(err) = fs::create_dir(&path) {
-                eprintln!("error creating cache dir {:?}: {}", path, err);
-                return;
+    // Location of the log of submitted answers.
+    pub fn submissions_path(&self) -> Option<PathBuf> {
+        self.cache_dir().map(|p| p.join("submissions"))
+    }
+
+    // Location of the timing history of runs.
+    pub fn history_path(&self) -> Option<PathBuf> {
+        self.cache_dir().map(|p| p.join("history"))
+    }
+
+    // Location of the cached input of day.
+    pub fn cache_path(&self, day: usize) -> Option<PathBuf> {
+        self.cache_dir().map(|p| p.join(day.to_string()))
+    }
+
+    fn get_cache(&self, day: usize) -> Option<String> {
+        fs::read_to_string(self.cache_path(day)?).ok()
+    }
+
+    fn put_cache(&self, day: usize, contents: &str) {
+        if let Err(err) = self.store(day, contents) {
+            eprintln!("{:#}", err);
+        }
+    }
+
+    // Saves contents as the cached input of day, unless it is an error response.
+    pub fn store(&self, day: usize, contents: &str) -> Result<PathBuf> {
+        if let Some(cause) = error_cause(contents) {
+            bail!("input for day {day} is an error response, {cause}");
+        }
+        let path = self
+            .cache_path(day)
+            .ok_or_else(|| anyhow!("no cache directory"))?;
+        let dir = path.parent().unwrap();
+        fs::create_dir_all(dir).with_context(|| format!("creating cache dir {:?}", dir))?;
+        fs::write(&path, contents).with_context(|| format!("writing cache file {:?}", path))?;
+        Ok(path)
+    }
+}
+
+// Bodies the input endpoint sends instead of the puzzle input,
+// with the likely cause.
+const ERROR_BODIES: &[(&str, &str)] = &[
+    (
+        "Please log in",
+        "the session cookie is missing or has expired",
+    ),
+    ("before it unlocks", "the puzzle is not unlocked yet"),
+    (
+        "404 Not Found",
+        "the puzzle does not exist or is not unlocked yet",
+    ),
+    (
+        "Internal Server Error",
+        "the session cookie is probably malformed",
+    ),
+];
+
+// Returns the likely cause if body is an error response rather than puzzle input.
+fn error_cause(body: &str) -> Option<&'static str> {
+    if body.trim().is_empty() {
+        return Some("the input is empty");
+    }
+    ERROR_BODIES
+        .iter()
+        .find(|(pat, _)| body.contains(pat))
+        .map(|&(_, cause)| cause)
+}
+
+fn check_response(status: StatusCode, body: &str) -> Result<()> {
+    if let Some(cause) = error_cause(body) {
+        bail!("HTTP {status}: {cause}");
+    }
+    if !status.is_success() {
+        bail!("HTTP {status}: unexpected response");
+    }
+    Ok(())
+}
+
+// Expands glob patterns into the list of matching files.
+// Patterns without matches are kept as is, so that reading them
+// reports the missing file. `-` stands for the standard input.
+pub fn expand_paths(patterns: &[String]) -> Result<Vec<String>> {
+    let mut v = vec![];
+    for pat in patterns {
+        let n = v.len();
+        if pat != "-" {
+            for p in glob::glob(pat).with_context(|| format!("invalid pattern {pat}"))? {
+                v.push(p?.to_string_lossy().into_owned());
             }
         }
-        path.push(day.to_string());
-        if let Err(err) = fs::write(&path, contents) {
-            eprintln!("error writing cache file {:?}: {}", path, err);
+        if v.len() == n {
+            v.push(pat.clone());
         }
     }
+    Ok(v)
+}
+
+// Reads puzzle input from path, or from the standard input if path is `-`.
+pub fn read_path(path: &str) -> Result<String> {
+    if path == "-" {
+        let mut s = String::new();
+        std::io::stdin()
+            .read_to_string(&mut s)
+            .context("reading stdin")?;
+        Ok(s)
+    } else {
+        fs::read_to_string(path).with_context(|| format!("reading {path}"))
+    }
+}
+
+// Fixtures directory of the crate, where the tests load the examples from.
+pub fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+}
+
+// Location of the nth example of day in the fixtures directory dir.
+// Like the day modules, fixtures of other years than ROOT_YEAR are in a folder of their own.
+pub fn fixture_path(dir: &Path, year: u32, day: usize, n: usize) -> PathBuf {
+    let dir = if year == crate::ROOT_YEAR {
+        dir.to_owned()
+    } else {
+        dir.join(format!("y{}", year))
+    };
+    dir.join(format!("day{:02}", day))
+        .join(format!("example{}.txt", n))
+}
+
+// Loads the nth example of day from the fixtures of the crate.
+pub fn example(year: u32, day: usize, n: usize) -> Result<String> {
+    let path = fixture_path(&fixtures_dir(), year, day, n);
+    fs::read_to_string(&path).with_context(|| format!("reading {:?}", path))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let login = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+        let early = "Please don't repeatedly request this endpoint before it unlocks! \
+The calendar countdown is synchronized with the server time; \
+the link will be enabled on the calendar the instant this puzzle becomes available.\n";
+
+        assert!(check_response(StatusCode::OK, "1 2\n3 4\n").is_ok());
+        assert!(check_response(StatusCode::BAD_REQUEST, login).is_err());
+        assert!(check_response(StatusCode::NOT_FOUND, early).is_err());
+        assert!(check_response(StatusCode::SERVICE_UNAVAILABLE, "1 2\n").is_err());
+
+        assert_eq!(
+            error_cause(login),
+            Some("the session cookie is missing or has expired")
+        );
+        assert_eq!(error_cause(early), Some("the puzzle is not unlocked yet"));
+        assert_eq!(error_cause(""), Some("the input is empty"));
+        assert_eq!(error_cause("2333133121414131402\n"), None);
+    }
 }
@@ -0,0 +1,89 @@
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+
+pub mod alloc;
+pub mod answer;
+pub mod check;
+pub mod context;
+pub mod extrapolate;
+pub mod grid;
+pub mod input;
+pub mod logger;
+pub mod quadmap;
+pub mod runner;
+pub mod search;
+pub mod solution;
+pub mod util;
+
+pub use answer::{Answer, Part};
+pub use context::Context;
+pub use grid::{Dir, Dir8, Grid, Point};
+pub use solution::{Parts, Solution};
+
+// Year of the day modules directly in src.
+// Days of other years live in their own module folder, such as src/y2023/dayNN.rs.
+pub const ROOT_YEAR: u32 = 2024;
+
+// Registered day functions for each year, generated by build.rs
+// from the dayNN.rs files found in src.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+pub type DayFunc = fn(&str, &Context) -> Result<Answer>;
+
+// Days registered for year, day 1 first.
+pub fn year_day_funcs(year: u32) -> Result<&'static [DayFunc]> {
+    YEARS
+        .iter()
+        .find_map(|&(y, fns)| (y == year).then_some(fns))
+        .ok_or_else(|| anyhow!("no days registered for {}", year))
+}
+
+pub fn day_func(year: u32, day: usize) -> Result<DayFunc> {
+    year_day_funcs(year)?
+        .get(day.wrapping_sub(1))
+        .copied()
+        .ok_or_else(|| anyhow!("day {} of {} is not registered", day, year))
+}
+
+// Years that have registered days.
+pub fn years() -> impl Iterator<Item = u32> {
+    YEARS.iter().map(|&(y, _)| y)
+}
+
+pub fn fmt_duration(d: Duration) -> String {
+    let ms = d.as_secs_f64() * 1000.0;
+    if ms < 1.0 {
+        return format!("{:.1}µs", ms * 1000.0);
+    }
+    if ms < 100.0 {
+        return format!("{:.1}ms", ms);
+    }
+    let nsec = d.as_secs();
+    let h = nsec / 3600;
+    let m = (nsec / 60) % 60;
+    let s = nsec % 60;
+    let ms = d.as_millis() % 1000;
+    let mut fmt = String::new();
+    if h > 0 {
+        fmt.push_str(&format!("{}h", h))
+    }
+    if h > 0 || m > 0 {
+        fmt.push_str(&format!("{}m", m))
+    }
+    fmt.push_str(&format!("{}.{:03}s", s, ms));
+    fmt
+}
+
+pub fn fmt_bytes(n: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB"];
+    let mut x = n as f64;
+    let mut unit = 0;
+    while x >= 1024.0 && unit + 1 < UNITS.len() {
+        x /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{}B", n),
+        _ => format!("{:.1}{}", x, UNITS[unit]),
+    }
+}
@@ -0,0 +1,190 @@
+use anyhow::{anyhow, Context as _, Result};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+const LEVELS: &[(&str, Level)] = &[
+    ("off", Level::Off),
+    ("error", Level::Error),
+    ("warn", Level::Warn),
+    ("info", Level::Info),
+    ("debug", Level::Debug),
+    ("trace", Level::Trace),
+];
+
+impl FromStr for Level {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        LEVELS
+            .iter()
+            .find_map(|&(name, l)| name.eq_ignore_ascii_case(s).then_some(l))
+            .ok_or_else(|| anyhow!("unknown log level {:?}", s))
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = LEVELS.iter().find(|&&(_, l)| l == *self).unwrap().0;
+        f.write_str(name)
+    }
+}
+
+// Most detailed level logged, for all days and for specific days.
+//
+// The format is a comma separated list of a level and `<day>=<level>` overrides,
+// such as `info,14=trace,17=off`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    default: Level,
+    days: BTreeMap<usize, Level>,
+}
+
+impl Filter {
+    pub fn new(default: Level) -> Self {
+        Self {
+            default,
+            days: BTreeMap::new(),
+        }
+    }
+
+    pub fn enabled(&self, day: usize, level: Level) -> bool {
+        level != Level::Off && level <= *self.days.get(&day).unwrap_or(&self.default)
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self::new(Level::Warn)
+    }
+}
+
+impl FromStr for Filter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut f = Filter::default();
+        for item in s.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match item.split_once('=') {
+                Some((day, level)) => {
+                    let day = day
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("invalid day in {:?}", item))?;
+                    f.days.insert(day, level.trim().parse()?);
+                }
+                None => f.default = item.parse()?,
+            }
+        }
+        Ok(f)
+    }
+}
+
+// Writes the messages that pass the filter to stderr or a file.
+// It is shared by the days running in parallel.
+pub struct Logger {
+    filter: Filter,
+    sink: Mutex<Box<dyn Write + Send>>,
+}
+
+impl Logger {
+    pub fn new(filter: Filter, sink: impl Write + Send + 'static) -> Self {
+        Self {
+            filter,
+            sink: Mutex::new(Box::new(sink)),
+        }
+    }
+
+    pub fn stderr(filter: Filter) -> Self {
+        Self::new(filter, io::stderr())
+    }
+
+    pub fn file(filter: Filter, path: &Path) -> Result<Self> {
+        let f = File::create(path).with_context(|| format!("creating {:?}", path))?;
+        Ok(Self::new(filter, f))
+    }
+
+    pub fn enabled(&self, day: usize, level: Level) -> bool {
+        self.filter.enabled(day, level)
+    }
+
+    pub fn log(&self, day: usize, level: Level, args: fmt::Arguments) {
+        if !self.enabled(day, level) {
+            return;
+        }
+        let mut sink = self.sink.lock().unwrap_or_else(|e| e.into_inner());
+        // Logging is best effort, a failing sink must not fail the solver.
+        let _ = match day {
+            0 => writeln!(sink, "{}: {}", level, args),
+            _ => writeln!(sink, "day{:02} {}: {}", day, level, args),
+        };
+    }
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Self::stderr(Filter::default())
+    }
+}
+
+impl fmt::Debug for Logger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Logger")
+            .field("filter", &self.filter)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+
+    // Sink that keeps what is written to it.
+    #[derive(Clone, Default)]
+    struct Buf(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buf {
+        fn write(&mut self, b: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(b)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn it_works() {
+        let f: Filter = "info, 14=trace,17=off".parse().unwrap();
+        assert!(f.enabled(1, Level::Info));
+        assert!(!f.enabled(1, Level::Debug));
+        assert!(f.enabled(14, Level::Trace));
+        assert!(!f.enabled(17, Level::Error));
+        assert!(!f.enabled(1, Level::Off));
+        assert!("14=loud".parse::<Filter>().is_err());
+        assert!("x=info".parse::<Filter>().is_err());
+
+        let buf = Buf::default();
+        let l = Logger::new("debug".parse().unwrap(), buf.clone());
+        l.log(3, Level::Debug, format_args!("{} sec", 100));
+        l.log(3, Level::Trace, format_args!("hidden"));
+        l.log(0, Level::Warn, format_args!("no day"));
+        let out = String::from_utf8(buf.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(out, "day03 debug: 100 sec\nwarn: no day\n");
+    }
+}
@@ -1,128 +1,268 @@
-use anyhow::Result;
-use clap::Parser;
-use once_cell::sync::OnceCell;
-use std::collections::HashSet;
+use anyhow::{anyhow, bail, Result};
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-const AOC_YEAR: u32 = 2024;
-
-mod extrapolate;
-mod grid;
-mod quadmap;
-mod util;
-
-// static_mod_funcs creates a static slice of `name` that
-// contains the methods `mname` as `mty` in the specified modules `m`.
-macro_rules! static_mod_funcs {
-    ( $name:ident, $mname:ident as $mty:ty, [ $( $m:ident ),* ] ) => {
-        $(
-            mod $m;
-        )*
-        static $name: &[$mty] = &[
-            $(
-                ($m::$mname as $mty),
-            )*
-        ];
-    }
-}
+// Year to run when --year is not given.
+const DEFAULT_YEAR: u32 = 2024;
 
-static_mod_funcs!(
-    DAY_FNS,
-    run as DayFunc,
-    [
-        day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
-        day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25
-    ]
-);
+mod bench;
+mod cache;
+mod history;
+mod puzzle;
+mod report;
+mod scaffold;
+mod select;
+mod submit;
 
-mod input;
-use input::InputSource;
+use aoc24::check::Expected;
+use aoc24::input::{self, InputSource};
+use aoc24::logger::{Filter, Level, Logger};
+use aoc24::{fmt_duration, runner, Context, Parts};
+use report::Report;
+use select::{get_day_funcs, listed_day_funcs, DaySelection};
 
 #[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Cli {
-    #[arg(short, long)]
+    /// Log debug messages of the solvers; short for --log debug
+    #[arg(short, long, global = true, conflicts_with = "log")]
     verbose: bool,
 
+    /// Log messages up to a level, such as `info`, with overrides
+    /// for specific days, such as `warn,14=debug,17=trace`
+    #[arg(long, global = true, value_name = "FILTER", default_value = "warn")]
+    log: Filter,
+
+    /// Write log messages to this file instead of stderr
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+
+    /// Puzzle year
+    #[arg(short, long, global = true, default_value_t = DEFAULT_YEAR)]
+    year: u32,
+
+    /// Base URL of the puzzle site
+    #[arg(long, global = true, env = "AOC_BASE_URL", default_value = input::BASE_URL)]
+    base_url: String,
+
+    /// Compare answers with the recorded ones
     #[arg(short, long)]
-    all: bool,
+    check: bool,
+
+    /// Record the current answers as the expected ones
+    #[arg(long)]
+    record: bool,
+
+    /// Read the input of the selected day from files instead of the cache;
+    /// `-` reads stdin, glob patterns are expanded
+    #[arg(short, long, value_name = "PATH",
+          conflicts_with_all = ["check", "record", "all", "slow", "fast"])]
+    input: Vec<String>,
+
+    /// Solve only this part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = report::Format::Text)]
+    format: report::Format,
+
+    /// Number of days to run in parallel; 0 uses all available cores.
+    /// Timings of parallel runs are not added to the history
+    #[arg(short, long, default_value_t = 1)]
+    jobs: usize,
+
+    /// Do not add the timings of this run to the history
+    #[arg(long)]
+    no_history: bool,
+
+    /// Report days running longer than this many seconds as timed out
+    #[arg(short, long, global = true, value_name = "SECS")]
+    timeout: Option<f64>,
+
+    #[command(flatten)]
+    days: DaySelection,
 
-    days: Vec<usize>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run selected days repeatedly and report timing statistics
+    Bench(bench::BenchArgs),
+
+    /// Download a puzzle description, and extract its examples into fixtures
+    Fetch(puzzle::FetchArgs),
+
+    /// Compare the latest timings with earlier runs and flag slowdowns
+    History(history::HistoryArgs),
+
+    /// Create a day module from the template
+    New(scaffold::NewArgs),
+
+    /// Submit the answer for a part of a day
+    Submit(submit::SubmitArgs),
+
+    /// Inspect the input cache
+    Cache {
+        #[command(subcommand)]
+        command: cache::CacheCommand,
+    },
 }
 
 impl Cli {
-    #[allow(unused)]
-    pub fn global() -> &'static Cli {
-        CLI_INSTANCE.get().expect("CLI is not initialized")
+    fn jobs(&self) -> usize {
+        match self.jobs {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        }
     }
-}
 
-static CLI_INSTANCE: OnceCell<Cli> = OnceCell::new();
+    fn parts(&self) -> Parts {
+        self.part.map_or(Parts::Both, |n| Parts::Only(n.into()))
+    }
 
-fn main() -> anyhow::Result<()> {
-    let is = InputSource::new()?;
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs_f64)
+    }
 
-    let cli = Cli::parse();
+    fn input_source(&self) -> InputSource {
+        InputSource::new(self.year).with_base_url(&self.base_url)
+    }
 
-    let dfs = get_day_funcs(&cli);
+    fn logger(&self) -> Result<Arc<Logger>> {
+        let filter = match self.verbose {
+            true => Filter::new(Level::Debug),
+            false => self.log.clone(),
+        };
+        let logger = match &self.log_file {
+            Some(path) => Logger::file(filter, path)?,
+            None => Logger::stderr(filter),
+        };
+        Ok(Arc::new(logger))
+    }
 
-    CLI_INSTANCE.set(cli).unwrap();
+    fn context(&self, day: usize, logger: &Arc<Logger>) -> Context {
+        Context::new(day, logger.clone()).with_parts(self.parts())
+    }
+}
 
-    for (i, f) in dfs {
-        let r = is.get(i);
-        let now = Instant::now();
-        let r = r.and_then(|s| f(&s));
-        print!("Day {:2}: ", i);
-        match r {
-            Ok(result) => println!("{}  ({})", result, fmt_duration(now.elapsed())),
-            Err(e) => {
-                println!();
-                eprintln!("{}", e);
-            }
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let logger = cli.logger()?;
+
+    match &cli.command {
+        Some(Command::Bench(args)) => {
+            return bench::main(&cli.input_source(), args, &logger, cli.timeout())
         }
+        Some(Command::Fetch(args)) => return puzzle::main(&cli.input_source(), args),
+        Some(Command::History(args)) => return history::main(&cli.input_source(), args),
+        Some(Command::New(args)) => return scaffold::main(cli.year, args),
+        Some(Command::Submit(args)) => return submit::main(&cli.input_source(), args, &logger),
+        Some(Command::Cache { command }) => return cache::main(&cli.input_source(), command),
+        None => {}
     }
 
-    Ok(())
-}
+    if !cli.input.is_empty() {
+        return run_files(&cli, &logger);
+    }
+
+    let is = cli.input_source();
+
+    let dfs = get_day_funcs(&is, &cli.days)?;
+
+    let mut expected = if cli.check || cli.record {
+        let path = is
+            .answers_path()
+            .ok_or_else(|| anyhow!("no cache directory"))?;
+        Some(Expected::load(&path)?)
+    } else {
+        None
+    };
+    let (check, record) = (cli.check, cli.record);
 
-fn fmt_duration(d: Duration) -> String {
-    let ms = d.as_secs_f64() * 1000.0;
-    if ms < 100.0 {
-        return format!("{:.1}ms", ms);
+    let mut report = Report::new(cli.format, cli.year);
+    let start = Instant::now();
+    // Wall time of the days summed, including waiting for their threads.
+    let mut solver_time = Duration::ZERO;
+    let mut nfail = 0;
+    let mut timings = vec![];
+    runner::run_ordered(
+        &dfs,
+        cli.jobs(),
+        |&(i, f)| {
+            let input = is.get(i);
+            let hash = input.as_deref().map(history::input_hash).ok();
+            let now = Instant::now();
+            let ctx = cli.context(i, &logger);
+            let r = input.and_then(|s| runner::run_isolated(f, s, ctx, cli.timeout()));
+            (r, now.elapsed(), hash)
+        },
+        |&(i, _), (r, elapsed, hash)| {
+            solver_time += elapsed;
+            nfail += report.day(
+                i,
+                cli.parts(),
+                None,
+                &r,
+                expected.as_ref().filter(|_| check),
+            );
+            if let (Ok(answer), Some(hash)) = (&r, hash) {
+                let e = history::Entry::new(cli.year, i, hash, answer.parse, answer.solve_time());
+                timings.push(e);
+            }
+            if let (Ok(answer), Some(exp)) = (&r, expected.as_mut().filter(|_| record)) {
+                for (n, part) in answer.parts() {
+                    exp.record(i, n, part);
+                }
+            }
+        },
+    );
+
+    if dfs.len() > 1 && report.is_text() {
+        println!(
+            "Total: {} wall clock, {} summed solver wall time",
+            fmt_duration(start.elapsed()),
+            fmt_duration(solver_time)
+        );
     }
-    let nsec = d.as_secs();
-    let h = nsec / 3600;
-    let m = (nsec / 60) % 60;
-    let s = nsec % 60;
-    let ms = d.as_millis() % 1000;
-    let mut fmt = String::new();
-    if h > 0 {
-        fmt.push_str(&format!("{}h", h))
+
+    // Times of single parts, or of days competing for cores with others,
+    // are not comparable with the whole days run one at a time in the history.
+    if !cli.no_history && cli.parts() == Parts::Both && cli.jobs() == 1 {
+        if let Err(e) = history::History::open(&is).and_then(|h| h.append(&mut timings)) {
+            eprintln!("Run history not saved: {:#}", e);
+        }
     }
-    if h > 0 || m > 0 {
-        fmt.push_str(&format!("{}m", m))
+
+    if let Some(exp) = expected.as_ref().filter(|_| record) {
+        exp.save()?;
+        eprintln!("Answers recorded in {:?}", exp.path());
     }
-    fmt.push_str(&format!("{}.{:03}s", s, ms));
-    fmt
-}
 
-pub fn verbose() -> bool {
-    CLI_INSTANCE.get().map(|cli| cli.verbose).unwrap_or(true)
+    if nfail > 0 {
+        bail!("{} answer(s) do not match the expected ones", nfail);
+    }
+
+    Ok(())
 }
 
-type DayFunc = fn(&str) -> Result<String>;
-
-fn get_day_funcs(cli: &Cli) -> Vec<(usize, DayFunc)> {
-    let v: Vec<(usize, DayFunc)> = DAY_FNS
-        .iter()
-        .enumerate()
-        .map(|(n, &f)| (n + 1, f))
-        .collect();
-    if !cli.days.is_empty() {
-        let s: HashSet<_> = cli.days.iter().collect();
-        v.into_iter().filter(|(x, _)| s.contains(&x)).collect()
-    } else if cli.all {
-        v
-    } else {
-        vec![*v.last().unwrap()]
+// Runs the selected day on each of the input files given on the command line.
+fn run_files(cli: &Cli, logger: &Arc<Logger>) -> Result<()> {
+    let dfs = listed_day_funcs(cli.year, &cli.days)?;
+    let [(i, f)] = dfs[..] else {
+        bail!("give exactly one day to run with --input");
+    };
+
+    let mut report = Report::new(cli.format, cli.year);
+    for path in input::expand_paths(&cli.input)? {
+        let r = input::read_path(&path)
+            .and_then(|s| runner::run_isolated(f, s, cli.context(i, logger), cli.timeout()));
+        report.day(i, cli.parts(), Some(&path), &r, None);
     }
+
+    Ok(())
 }
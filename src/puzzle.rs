@@ -0,0 +1,303 @@
+use anyhow::{anyhow, Context, Result};
+use aoc24::input::{fixture_path, fixtures_dir, InputSource};
+use clap::Args;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Args)]
+pub struct FetchArgs {
+    day: usize,
+
+    /// Use a saved puzzle page instead of downloading it
+    #[arg(short, long, value_name = "FILE")]
+    page: Option<PathBuf>,
+
+    /// Directory for the example fixtures; the fixtures of the crate by default
+    #[arg(long, value_name = "DIR")]
+    fixtures: Option<PathBuf>,
+
+    /// Overwrite existing fixtures
+    #[arg(short, long)]
+    force: bool,
+}
+
+pub fn main(is: &InputSource, args: &FetchArgs) -> Result<()> {
+    let day = args.day;
+    let html = match &args.page {
+        Some(path) => fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?,
+        None => is.get_page(day)?,
+    };
+
+    let md = to_markdown(&html);
+    if md.is_empty() {
+        return Err(anyhow!(
+            "no puzzle description found on the page of day {day}"
+        ));
+    }
+    if let Some(path) = is.page_path(day, "md") {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, &md).with_context(|| format!("writing {:?}", path))?;
+        println!("Description: {:?}", path);
+    }
+
+    let fixtures = args.fixtures.clone().unwrap_or_else(fixtures_dir);
+    for (i, ex) in examples(&html).iter().enumerate() {
+        let path = fixture_path(&fixtures, is.year(), day, i + 1);
+        if path.exists() && !args.force {
+            println!("Example {}: {:?} exists, skipped", i + 1, path);
+            continue;
+        }
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, ex).with_context(|| format!("writing {:?}", path))?;
+        println!("Example {}: {:?}", i + 1, path);
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'a> {
+    Open(&'a str, &'a str),
+    Close(&'a str),
+    Text(&'a str),
+}
+
+// Splits html into tags and text. Comments and declarations are dropped.
+fn tokens(html: &str) -> Vec<Token<'_>> {
+    let mut v = vec![];
+    let mut s = html;
+    while !s.is_empty() {
+        let Some(i) = s.find('<') else {
+            v.push(Token::Text(s));
+            break;
+        };
+        if i > 0 {
+            v.push(Token::Text(&s[..i]));
+        }
+        s = &s[i..];
+        if let Some(rest) = s.strip_prefix("<!--") {
+            s = rest.find("-->").map_or("", |j| &rest[j + 3..]);
+            continue;
+        }
+        let Some(j) = s.find('>') else {
+            v.push(Token::Text(s));
+            break;
+        };
+        let tag = s[1..j].trim_end_matches('/');
+        s = &s[j + 1..];
+        if let Some(name) = tag.strip_prefix('/') {
+            v.push(Token::Close(name.trim()));
+        } else if !tag.starts_with('!') {
+            let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            v.push(Token::Open(name, attrs));
+        }
+    }
+    v
+}
+
+fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let pat = format!("{}=\"", name);
+    let i = attrs.find(&pat)? + pat.len();
+    let j = attrs[i..].find('"')?;
+    Some(&attrs[i..i + j])
+}
+
+fn decode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut s = s;
+    while let Some(i) = s.find('&') {
+        out.push_str(&s[..i]);
+        s = &s[i..];
+        let end = s.find(';').filter(|&j| j < 10);
+        let c = end.and_then(|j| match &s[1..j] {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            e => {
+                let n = match e.strip_prefix("#x") {
+                    Some(h) => u32::from_str_radix(h, 16).ok(),
+                    None => e.strip_prefix('#').and_then(|d| d.parse().ok()),
+                };
+                n.and_then(char::from_u32)
+            }
+        });
+        match (c, end) {
+            (Some(c), Some(j)) => {
+                out.push(c);
+                s = &s[j + 1..];
+            }
+            _ => {
+                out.push('&');
+                s = &s[1..];
+            }
+        }
+    }
+    out.push_str(s);
+    out
+}
+
+// Contents of the puzzle description articles, one per part.
+fn articles(html: &str) -> Vec<&str> {
+    let mut v = vec![];
+    let mut s = html;
+    while let Some(i) = s.find("<article") {
+        s = &s[i..];
+        let Some(j) = s.find('>') else {
+            break;
+        };
+        s = &s[j + 1..];
+        let k = s.find("</article>").unwrap_or(s.len());
+        v.push(&s[..k]);
+        s = &s[k..];
+    }
+    v
+}
+
+// Converts the puzzle description in the page html to Markdown.
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut links = vec![];
+    let mut in_pre = false;
+    let mut in_code = false;
+    for article in articles(html) {
+        for t in tokens(article) {
+            match t {
+                // Skip the line breaks between block elements.
+                Token::Text(s) if !in_pre && s.trim().is_empty() && out.ends_with('\n') => {}
+                Token::Text(s) => out.push_str(&decode(s)),
+                Token::Open("h2", _) => out.push_str("## "),
+                Token::Close("h2" | "p") => out.push_str("\n\n"),
+                Token::Close("ul") => out.push('\n'),
+                Token::Open("pre", _) => {
+                    out.push_str("```\n");
+                    in_pre = true;
+                }
+                Token::Close("pre") => {
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```\n\n");
+                    in_pre = false;
+                }
+                Token::Open("code", _) if !in_pre => {
+                    out.push('`');
+                    in_code = true;
+                }
+                Token::Close("code") if !in_pre => {
+                    out.push('`');
+                    in_code = false;
+                }
+                Token::Open("em", _) | Token::Close("em") if !in_pre && !in_code => {
+                    out.push_str("**")
+                }
+                Token::Open("a", attrs) => {
+                    out.push('[');
+                    links.push(attr(attrs, "href").unwrap_or_default());
+                }
+                Token::Close("a") => {
+                    out.push_str(&format!("]({})", links.pop().unwrap_or_default()));
+                }
+                Token::Open("li", _) => out.push_str("- "),
+                Token::Close("li") => out.push('\n'),
+                _ => {}
+            }
+        }
+    }
+    let n = out.trim_end().len();
+    out.truncate(n);
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+// Extracts the text of the preformatted blocks in the puzzle description.
+pub fn examples(html: &str) -> Vec<String> {
+    let mut v = vec![];
+    let mut cur: Option<String> = None;
+    for article in articles(html) {
+        for t in tokens(article) {
+            match (t, &mut cur) {
+                (Token::Open("pre", _), _) => cur = Some(String::new()),
+                (Token::Close("pre"), _) => v.extend(cur.take()),
+                (Token::Text(s), Some(ex)) => ex.push_str(&decode(s)),
+                _ => {}
+            }
+        }
+    }
+    v
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<head><title>Day 9 - Advent of Code 2024</title></head>
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 9: Disk Fragmenter ---</h2><p>The <a href="/2024/day/9">disk map</a> uses a <em>dense format</em>:</p>
+<pre><code>2333133121414131402
+</code></pre>
+<!-- hidden -->
+<p>The checksum is <code><em>1928</em></code> &amp; the layout is <code>0..111&lt;..22</code>.</p>
+<ul>
+<li>First, <em>move</em> blocks.</li>
+<li>Then compute it.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>6334655979668</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><pre><code>00...111...2...333.44.5555.6666.777.888899
+<em>0099</em>.111...2...333.44.5555.6666.777.8888..
+</code></pre>
+</article>
+</main>
+</body>
+</html>
+"#;
+
+    #[test]
+    fn it_works() {
+        let md = to_markdown(PAGE);
+        assert_eq!(
+            md,
+            r#"## --- Day 9: Disk Fragmenter ---
+
+The [disk map](/2024/day/9) uses a **dense format**:
+
+```
+2333133121414131402
+```
+
+The checksum is `1928` & the layout is `0..111<..22`.
+
+- First, **move** blocks.
+- Then compute it.
+
+## --- Part Two ---
+
+```
+00...111...2...333.44.5555.6666.777.888899
+0099.111...2...333.44.5555.6666.777.8888..
+```
+"#
+        );
+
+        let ex = examples(PAGE);
+        assert_eq!(ex.len(), 2);
+        assert_eq!(ex[0], "2333133121414131402\n");
+        assert!(ex[1].starts_with("00...111"));
+        assert!(ex[1].contains("\n0099.111"));
+    }
+
+    #[test]
+    fn decode_works() {
+        assert_eq!(
+            decode("a &lt;b&gt; &amp;&#39;&#x41; &unknown; &"),
+            "a <b> &'A &unknown; &"
+        );
+    }
+}
@@ -3,7 +3,6 @@ use std::collections::HashMap;
 
 const SIZE: i32 = 16; // x/y size of one block
 
-#[allow(unused)]
 #[derive(Debug)]
 pub struct Map<T> {
     zero: T,
@@ -11,7 +10,6 @@ pub struct Map<T> {
     bounds: Bounds,
 }
 
-#[allow(unused)]
 impl<T: Copy> Map<T> {
     pub fn new(zero: T) -> Map<T> {
         Map {
@@ -66,7 +64,7 @@ impl<T: Copy> Map<T> {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone)]
 pub struct Bounds {
     pub min: (i32, i32),
     pub max: (i32, i32),
@@ -74,10 +72,7 @@ pub struct Bounds {
 
 impl Bounds {
     pub fn new() -> Bounds {
-        Self {
-            min: (0, 0),
-            max: (0, 0),
-        }
+        Self::default()
     }
 
     pub fn is_empty(&self) -> bool {
@@ -0,0 +1,346 @@
+use anyhow::Result;
+use aoc24::answer::Answer;
+use aoc24::check::{Expected, Status};
+use aoc24::runner::RunError;
+use aoc24::{fmt_bytes, fmt_duration, Parts};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human readable lines
+    Text,
+    /// One JSON object per line
+    Json,
+    /// Comma separated values with a header
+    Csv,
+}
+
+// Result of one part of a day, or of the whole day if it failed before
+// producing answers.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub year: u32,
+    pub day: usize,
+    pub part: Option<usize>,
+    pub input: Option<String>,
+    pub status: &'static str,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub error: Option<String>,
+    pub parse_ms: f64,
+    // None if the part shares the solve time of the previous one.
+    pub solve_ms: Option<f64>,
+    pub alloc_count: Option<u64>,
+    pub alloc_bytes: Option<u64>,
+    pub alloc_peak: Option<u64>,
+}
+
+const CSV_HEADER: &str = "year,day,part,input,status,answer,expected,error,parse_ms,solve_ms,\
+alloc_count,alloc_bytes,alloc_peak";
+
+pub struct Report {
+    format: Format,
+    year: u32,
+    header: bool,
+}
+
+impl Report {
+    pub fn new(format: Format, year: u32) -> Self {
+        Self {
+            format,
+            year,
+            header: false,
+        }
+    }
+
+    pub fn is_text(&self) -> bool {
+        self.format == Format::Text
+    }
+
+    // Reports the result of the parts of day run on the input named label,
+    // and returns the number of parts that failed the check against expected answers.
+    pub fn day(
+        &mut self,
+        day: usize,
+        parts: Parts,
+        label: Option<&str>,
+        r: &Result<Answer>,
+        expected: Option<&Expected>,
+    ) -> usize {
+        if self.format == Format::Text {
+            match label {
+                Some(l) => print!("Day {:2} {}:", day, l),
+                None => print!("Day {:2}:", day),
+            }
+            return match r {
+                Ok(answer) => print_answer(day, answer, expected),
+                Err(e) => {
+                    // Selected parts with an expected answer fail along with the day.
+                    let fails: Vec<String> = expected
+                        .into_iter()
+                        .flat_map(|exp| exp.parts(day))
+                        .filter(|&(n, _)| parts.contains(n))
+                        .map(|(_, want)| format!(" - [{}]", Status::Fail(want.to_owned())))
+                        .collect();
+                    println!("{}", fails.join(" "));
+                    eprintln!("{:#}", e);
+                    fails.len()
+                }
+            };
+        }
+
+        let (records, nfail) = self.records(day, parts, label, r, expected);
+        for rec in records {
+            match self.format {
+                Format::Json => println!("{}", serde_json::to_string(&rec).unwrap()),
+                Format::Csv => {
+                    if !self.header {
+                        println!("{}", CSV_HEADER);
+                        self.header = true;
+                    }
+                    println!("{}", csv_row(&rec));
+                }
+                Format::Text => unreachable!(),
+            }
+        }
+        nfail
+    }
+
+    fn records(
+        &self,
+        day: usize,
+        parts: Parts,
+        label: Option<&str>,
+        r: &Result<Answer>,
+        expected: Option<&Expected>,
+    ) -> (Vec<Record>, usize) {
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        let rec = |part, status| Record {
+            year: self.year,
+            day,
+            part,
+            input: label.map(|s| s.to_owned()),
+            status,
+            answer: None,
+            expected: None,
+            error: None,
+            parse_ms: 0.0,
+            solve_ms: Some(0.0),
+            alloc_count: None,
+            alloc_bytes: None,
+            alloc_peak: None,
+        };
+
+        let answer = match r {
+            Ok(a) => a,
+            Err(e) => {
+                let status = match e.downcast_ref::<RunError>() {
+                    Some(RunError::Panicked(_)) => "panicked",
+                    Some(RunError::TimedOut(_)) => "timed out",
+                    None => "error",
+                };
+                let error = format!("{:#}", e);
+                // Selected parts with an expected answer fail along with the day.
+                let fails: Vec<Record> = expected
+                    .into_iter()
+                    .flat_map(|exp| exp.parts(day))
+                    .filter(|&(n, _)| parts.contains(n))
+                    .map(|(n, want)| {
+                        let mut x = rec(Some(n), "fail");
+                        x.expected = Some(want.to_owned());
+                        x.error = Some(error.clone());
+                        x
+                    })
+                    .collect();
+                if !fails.is_empty() {
+                    let n = fails.len();
+                    return (fails, n);
+                }
+                let mut x = rec(None, status);
+                x.error = Some(error);
+                return (vec![x], 0);
+            }
+        };
+
+        let mut nfail = 0;
+        let v = answer
+            .parts()
+            .map(|(n, part)| {
+                let check = expected.map(|exp| exp.check(day, n, part));
+                let status = match (&part.result, &check) {
+                    (_, Some(Status::Fail(_))) => "fail",
+                    (Err(_), _) => "error",
+                    (Ok(_), Some(Status::Pass)) => "pass",
+                    (Ok(_), Some(Status::Unknown)) => "unknown",
+                    (Ok(_), None) => "ok",
+                };
+                if status == "fail" {
+                    nfail += 1;
+                }
+                let mut x = rec(Some(n), status);
+                x.answer = part.value().map(|s| s.to_owned());
+                x.expected = match check {
+                    Some(Status::Pass) => x.answer.clone(),
+                    Some(Status::Fail(want)) => Some(want),
+                    _ => None,
+                };
+                x.error = part.result.as_ref().err().map(|e| format!("{:#}", e));
+                x.parse_ms = ms(answer.parse);
+                x.solve_ms = part.elapsed.map(ms);
+                if let Some(a) = answer.alloc {
+                    x.alloc_count = Some(a.count);
+                    x.alloc_bytes = Some(a.bytes);
+                    x.alloc_peak = Some(a.peak);
+                }
+                x
+            })
+            .collect();
+        (v, nfail)
+    }
+}
+
+// Prints the answer line for a day, and returns the number of
+// parts that failed the check against expected answers.
+fn print_answer(day: usize, answer: &Answer, expected: Option<&Expected>) -> usize {
+    let mut nfail = 0;
+    let line: Vec<String> = answer
+        .parts()
+        .map(|(n, part)| {
+            let mut s = match &part.result {
+                Ok(v) => format!(
+                    "{} ({})",
+                    v,
+                    part.elapsed.map_or("shared".into(), fmt_duration)
+                ),
+                Err(_) => String::from("-"),
+            };
+            if let Some(exp) = expected {
+                let status = exp.check(day, n, part);
+                if let Status::Fail(_) = status {
+                    nfail += 1;
+                }
+                s.push_str(&format!(" [{}]", status));
+            }
+            s
+        })
+        .collect();
+    let alloc = answer.alloc.map_or(String::new(), |a| {
+        let (bytes, peak) = (fmt_bytes(a.bytes), fmt_bytes(a.peak));
+        format!("  alloc {} ({}, peak {})", a.count, bytes, peak)
+    });
+    println!(
+        " {}  parse {}{}",
+        line.join("  "),
+        fmt_duration(answer.parse),
+        alloc
+    );
+    for (n, part) in answer.parts() {
+        if let Err(e) = &part.result {
+            eprintln!("Day {} part {}: {}", day, n, e);
+        }
+    }
+    nfail
+}
+
+fn csv_row(r: &Record) -> String {
+    let opt = |x: &Option<String>| x.as_deref().map(csv_field).unwrap_or_default();
+    let opt_num = |x: Option<u64>| x.map(|n| n.to_string()).unwrap_or_default();
+    [
+        r.year.to_string(),
+        r.day.to_string(),
+        r.part.map(|n| n.to_string()).unwrap_or_default(),
+        opt(&r.input),
+        csv_field(r.status),
+        opt(&r.answer),
+        opt(&r.expected),
+        opt(&r.error),
+        format!("{:.3}", r.parse_ms),
+        r.solve_ms
+            .map(|ms| format!("{:.3}", ms))
+            .unwrap_or_default(),
+        opt_num(r.alloc_count),
+        opt_num(r.alloc_bytes),
+        opt_num(r.alloc_peak),
+    ]
+    .join(",")
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::anyhow;
+    use aoc24::alloc::AllocStats;
+    use aoc24::answer::Part;
+    use std::path::Path;
+
+    #[test]
+    fn it_works() {
+        let report = Report::new(Format::Csv, 2024);
+        let mut answer = Answer::new(
+            Part::eval(|| "4,6,3"),
+            Part::try_eval(|| -> Result<usize> { Err(anyhow!("no \"path\"")) }),
+        );
+        answer.parse = Duration::from_micros(1500);
+        answer.alloc = Some(AllocStats {
+            count: 3,
+            bytes: 100,
+            peak: 64,
+        });
+
+        let (v, nfail) = report.records(17, Parts::Both, None, &Ok(answer), None);
+        assert_eq!(nfail, 0);
+        assert_eq!(v.len(), 2);
+        assert_eq!(v[0].status, "ok");
+        assert_eq!(v[0].parse_ms, 1.5);
+        assert!(csv_row(&v[0]).starts_with("2024,17,1,,ok,\"4,6,3\",,,1.500,"));
+        assert!(csv_row(&v[0]).ends_with(",3,100,64"));
+        assert_eq!(v[1].status, "error");
+        assert!(csv_row(&v[1]).starts_with("2024,17,2,,error,,,\"no \"\"path\"\"\","));
+
+        // Part 2 solved with part 1 has no time of its own.
+        let (v, _) = report.records(6, Parts::Both, None, &Ok(Answer::joint(|| (1, 2))), None);
+        assert!(v[0].solve_ms.is_some());
+        assert_eq!(v[1].solve_ms, None);
+        assert!(csv_row(&v[1]).starts_with("2024,6,2,,ok,2,,,0.000,,"));
+
+        let r = Err(RunError::Panicked(String::from("oops")).into());
+        let (v, _) = report.records(3, Parts::Both, Some("ex.txt"), &r, None);
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].part, None);
+        assert_eq!(v[0].status, "panicked");
+        assert_eq!(
+            serde_json::to_string(&v[0]).unwrap(),
+            r#"{"year":2024,"day":3,"part":null,"input":"ex.txt","status":"panicked","answer":null,"expected":null,"error":"panicked: oops","parse_ms":0.0,"solve_ms":0.0,"alloc_count":null,"alloc_bytes":null,"alloc_peak":null}"#
+        );
+
+        // A day that fails fails the parts it has answers for.
+        let mut exp = Expected::load(Path::new("/nonexistent/answers")).unwrap();
+        exp.record(3, 1, &Part::eval(|| 11));
+        exp.record(3, 2, &Part::eval(|| 31));
+        exp.record(4, 1, &Part::eval(|| 1));
+        let (v, nfail) = report.records(3, Parts::Both, None, &r, Some(&exp));
+        assert_eq!(nfail, 2);
+        assert_eq!(v.len(), 2);
+        assert_eq!((v[1].part, v[1].status), (Some(2), "fail"));
+        assert_eq!(v[1].expected.as_deref(), Some("31"));
+        assert!(csv_row(&v[0]).starts_with("2024,3,1,,fail,,11,panicked: oops,"));
+        let (_, nfail) = report.records(5, Parts::Both, None, &r, Some(&exp));
+        assert_eq!(nfail, 0);
+
+        // Parts that were not asked for don't fail.
+        let (v, nfail) = report.records(3, Parts::Only(2), None, &r, Some(&exp));
+        assert_eq!(nfail, 1);
+        assert_eq!(v.len(), 1);
+        assert_eq!((v[0].part, v[0].status), (Some(2), "fail"));
+    }
+}
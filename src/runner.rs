@@ -0,0 +1,206 @@
+use crate::alloc;
+use crate::answer::Answer;
+use crate::context::Context;
+use crate::{fmt_duration, DayFunc};
+use anyhow::Result;
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+// Stack size of solver threads, generous for recursive solutions.
+const STACK_SIZE: usize = 64 << 20;
+
+#[derive(Debug)]
+pub enum RunError {
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RunError::Panicked(msg) => write!(f, "panicked: {}", msg),
+            RunError::TimedOut(d) => write!(f, "timed out after {}", fmt_duration(*d)),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+// Runs f on input with ctx in a thread of its own, so that a panic is reported
+// as an error of this day only.
+//
+// If timeout elapses first, RunError::TimedOut is returned. The solver
+// thread can't be stopped, it is left running in the background.
+pub fn run_isolated(
+    f: DayFunc,
+    input: String,
+    ctx: Context,
+    timeout: Option<Duration>,
+) -> Result<Answer> {
+    let (tx, rx) = mpsc::channel();
+    let h = thread::Builder::new()
+        .name(format!("day{:02}", ctx.day))
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let (r, stats) = alloc::measure(|| f(&input, &ctx));
+            let r = r.map(|answer| Answer {
+                alloc: stats,
+                ..answer
+            });
+            // The receiver is gone if the runner timed out.
+            let _ = tx.send(r);
+        })?;
+
+    let r = match timeout {
+        Some(t) => rx.recv_timeout(t).map_err(|e| match e {
+            mpsc::RecvTimeoutError::Timeout => Some(RunError::TimedOut(t)),
+            mpsc::RecvTimeoutError::Disconnected => None,
+        }),
+        None => rx.recv().map_err(|_| None),
+    };
+
+    match r {
+        Ok(r) => r,
+        Err(Some(e)) => Err(e.into()),
+        // The sender was dropped without a result, so the solver panicked.
+        Err(None) => match h.join() {
+            Err(p) => Err(RunError::Panicked(panic_message(p)).into()),
+            Ok(()) => unreachable!("solver finished without a result"),
+        },
+    }
+}
+
+// Calls f on the current thread, and returns a panic in it as RunError::Panicked.
+// Cheaper than run_isolated for repeated runs, but without a timeout.
+pub fn catch_panic<R>(f: impl FnOnce() -> Result<R>) -> Result<R> {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|p| Err(RunError::Panicked(panic_message(p)).into()))
+}
+
+// Runs job for each of items on up to jobs threads,
+// and passes the results to emit in the order of items.
+pub fn run_ordered<T, R, J, E>(items: &[T], jobs: usize, job: J, mut emit: E)
+where
+    T: Sync,
+    R: Send,
+    J: Fn(&T) -> R + Sync,
+    E: FnMut(&T, R),
+{
+    if jobs <= 1 {
+        for item in items {
+            emit(item, job(item));
+        }
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|s| {
+        for _ in 0..jobs.min(items.len()) {
+            let tx = tx.clone();
+            let (next, job) = (&next, &job);
+            s.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else {
+                    break;
+                };
+                if tx.send((i, job(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        // Results arriving early wait here for their turn.
+        let mut pending = BTreeMap::new();
+        let mut cur = 0;
+        for (i, r) in rx {
+            pending.insert(i, r);
+            while let Some(r) = pending.remove(&cur) {
+                emit(&items[cur], r);
+                cur += 1;
+            }
+        }
+    });
+}
+
+fn panic_message(p: Box<dyn Any + Send>) -> String {
+    if let Some(s) = p.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = p.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::answer::Part;
+
+    fn ok(_: &str, _: &Context) -> Result<Answer> {
+        Ok(Answer::single(Part::eval(|| 42)))
+    }
+
+    fn panics(input: &str, _: &Context) -> Result<Answer> {
+        let n: usize = input.parse().unwrap();
+        Ok(Answer::single(Part::eval(|| n)))
+    }
+
+    fn hangs(_: &str, ctx: &Context) -> Result<Answer> {
+        thread::sleep(Duration::from_secs(10));
+        ok("", ctx)
+    }
+
+    #[test]
+    fn ordered_works() {
+        let items: Vec<u64> = (0..20).collect();
+        for jobs in [1, 4] {
+            let mut v = vec![];
+            run_ordered(
+                &items,
+                jobs,
+                |&n| {
+                    thread::sleep(Duration::from_millis(20 - n));
+                    n * n
+                },
+                |&n, r| v.push((n, r)),
+            );
+            let want: Vec<_> = items.iter().map(|&n| (n, n * n)).collect();
+            assert_eq!(v, want);
+        }
+    }
+
+    #[test]
+    fn it_works() {
+        let ctx = |day| Context::new(day, Default::default());
+        let a = run_isolated(ok, String::new(), ctx(1), None).unwrap();
+        assert_eq!(a.part1.unwrap().value(), Some("42"));
+
+        let e = run_isolated(panics, "x".into(), ctx(2), None).unwrap_err();
+        assert!(matches!(
+            e.downcast_ref::<RunError>(),
+            Some(RunError::Panicked(msg)) if msg.contains("ParseIntError")
+        ));
+
+        let e = catch_panic(|| panics("x", &ctx(2))).unwrap_err();
+        assert!(matches!(
+            e.downcast_ref::<RunError>(),
+            Some(RunError::Panicked(msg)) if msg.contains("ParseIntError")
+        ));
+
+        let t = Duration::from_millis(10);
+        let e = run_isolated(hangs, String::new(), ctx(3), Some(t)).unwrap_err();
+        assert!(matches!(
+            e.downcast_ref::<RunError>(),
+            Some(RunError::TimedOut(_))
+        ));
+    }
+}
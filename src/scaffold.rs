@@ -0,0 +1,103 @@
+use anyhow::{bail, Context, Result};
+use aoc24::input::{fixture_path, fixtures_dir};
+use aoc24::ROOT_YEAR;
+use clap::Args;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Template of a day module, with __YEAR__ and __DAY__ standing for the year and day.
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+#[derive(Debug, Args)]
+pub struct NewArgs {
+    /// Day to create; the first day without a module if omitted
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+}
+
+pub fn main(year: u32, args: &NewArgs) -> Result<()> {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let path = scaffold(&src, &fixtures_dir(), year, args.day.map(usize::from))?;
+    println!("Created {:?}", path);
+    Ok(())
+}
+
+// Location of the module of day in the source directory src.
+fn module_path(src: &Path, year: u32, day: usize) -> PathBuf {
+    let dir = if year == ROOT_YEAR {
+        src.to_owned()
+    } else {
+        src.join(format!("y{}", year))
+    };
+    dir.join(format!("day{:02}.rs", day))
+}
+
+// Creates the module of day from the template, and returns its path.
+// The build script picks it up from there and registers it.
+// An empty first example is added to the fixtures if there is none yet,
+// so that the test of the template passes until the puzzle is fetched.
+fn scaffold(src: &Path, fixtures: &Path, year: u32, day: Option<usize>) -> Result<PathBuf> {
+    let day = match day {
+        Some(d) => d,
+        None => (1..=25)
+            .find(|&d| !module_path(src, year, d).exists())
+            .context("all days already exist")?,
+    };
+    let path = module_path(src, year, day);
+    if path.exists() {
+        bail!("day {} of {} already exists in {:?}", day, year, path);
+    }
+    if day > 1 && !module_path(src, year, day - 1).exists() {
+        bail!("day {} of {} must be created first", day - 1, year);
+    }
+
+    fs::create_dir_all(path.parent().unwrap())?;
+    let module = TEMPLATE
+        .replace("__YEAR__", &year.to_string())
+        .replace("__DAY__", &day.to_string());
+    fs::write(&path, module).with_context(|| format!("writing {:?}", path))?;
+
+    let example = fixture_path(fixtures, year, day, 1);
+    if !example.exists() {
+        fs::create_dir_all(example.parent().unwrap())?;
+        fs::write(&example, "").with_context(|| format!("writing {:?}", example))?;
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let (src, fixtures) = (root.join("src"), root.join("fixtures"));
+        fs::create_dir_all(&src).unwrap();
+        let scaffold = |year, day| scaffold(&src, &fixtures, year, day);
+
+        let example = fixtures.join("day02").join("example1.txt");
+        fs::create_dir_all(example.parent().unwrap()).unwrap();
+        fs::write(&example, "fetched\n").unwrap();
+
+        let p = scaffold(ROOT_YEAR, None).unwrap();
+        assert_eq!(p, src.join("day01.rs"));
+        assert!(fs::read_to_string(&p)
+            .unwrap()
+            .contains("example(2024, 1, 1)"));
+        assert_eq!(
+            fs::read_to_string(fixtures.join("day01").join("example1.txt")).unwrap(),
+            ""
+        );
+        assert!(scaffold(ROOT_YEAR, Some(1)).is_err());
+        assert!(scaffold(ROOT_YEAR, Some(3)).is_err());
+        assert_eq!(scaffold(ROOT_YEAR, None).unwrap(), src.join("day02.rs"));
+        assert_eq!(fs::read_to_string(&example).unwrap(), "fetched\n");
+
+        let p = scaffold(2023, None).unwrap();
+        assert_eq!(p, src.join("y2023").join("day01.rs"));
+        assert!(fixtures.join("y2023/day01/example1.txt").exists());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,278 @@
+use crate::grid::{Grid, Point};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+// Outcome of a search from a set of start states.
+//
+// It keeps the distance of every state reached, and all the predecessors
+// through which a state is reached at that distance. The predecessors form
+// the DAG of the optimal paths.
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    dist: HashMap<S, usize>,
+    preds: HashMap<S, Vec<S>>,
+    goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    fn new() -> Self {
+        Self {
+            dist: HashMap::new(),
+            preds: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    // Records that s is reached from pred at distance d,
+    // and returns whether that improves on what was known.
+    fn relax(&mut self, s: &S, pred: &S, d: usize) -> bool {
+        match self.dist.get(s) {
+            Some(&x) if x < d => false,
+            Some(&x) if x == d => {
+                self.preds.entry(s.clone()).or_default().push(pred.clone());
+                false
+            }
+            _ => {
+                self.dist.insert(s.clone(), d);
+                self.preds.insert(s.clone(), vec![pred.clone()]);
+                true
+            }
+        }
+    }
+
+    // First goal state reached, the closest one to the start.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref()
+    }
+
+    pub fn dist(&self, s: &S) -> Option<usize> {
+        self.dist.get(s).copied()
+    }
+
+    // Distance of every state reached.
+    pub fn distances(&self) -> &HashMap<S, usize> {
+        &self.dist
+    }
+
+    // States just before s on the optimal paths to s.
+    pub fn preds(&self, s: &S) -> &[S] {
+        self.preds.get(s).map_or(&[], |v| v)
+    }
+
+    // One optimal path from a start state to s, both included.
+    pub fn path_to(&self, s: &S) -> Option<Vec<S>> {
+        self.dist.get(s)?;
+        let mut path = vec![s.clone()];
+        while let Some(p) = self.preds(path.last().unwrap()).first() {
+            path.push(p.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // Number of optimal paths from the start states to s.
+    pub fn count_paths(&self, s: &S) -> usize {
+        let mut memo = HashMap::new();
+        self.count_paths_memo(s, &mut memo)
+    }
+
+    fn count_paths_memo(&self, s: &S, memo: &mut HashMap<S, usize>) -> usize {
+        if let Some(&n) = memo.get(s) {
+            return n;
+        }
+        let n = match (self.dist.get(s), self.preds(s)) {
+            (None, _) => 0,
+            (_, []) => 1,
+            (_, v) => v.iter().map(|p| self.count_paths_memo(p, memo)).sum(),
+        };
+        memo.insert(s.clone(), n);
+        n
+    }
+
+    // States on any of the optimal paths to the states in ends.
+    pub fn on_paths_to<'a>(&self, ends: impl IntoIterator<Item = &'a S>) -> HashSet<S>
+    where
+        S: 'a,
+    {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<S> = ends
+            .into_iter()
+            .filter(|s| self.dist.contains_key(s))
+            .cloned()
+            .collect();
+        while let Some(s) = stack.pop() {
+            if seen.insert(s.clone()) {
+                stack.extend(self.preds(&s).iter().cloned());
+            }
+        }
+        seen
+    }
+
+    // Distance map over a grid, with the shortest distance of
+    // the states at each point given by pos.
+    pub fn distance_grid<F>(&self, dims: (i32, i32), pos: F) -> Grid<Option<usize>>
+    where
+        F: Fn(&S) -> Point,
+    {
+        let mut g = Grid::new(dims, None);
+        for (s, &d) in &self.dist {
+            if let Some(x) = g.get_mut(pos(s)) {
+                *x = Some(x.map_or(d, |x: usize| x.min(d)));
+            }
+        }
+        g
+    }
+}
+
+// Breadth-first search, for moves that all cost 1.
+//
+// The search stops once every state as close as the first one satisfying
+// goal is done, so that all the optimal paths to it are known.
+// A goal that is never satisfied explores all reachable states.
+pub fn bfs<S, N, I, G>(starts: impl IntoIterator<Item = S>, mut next: N, mut goal: G) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    G: FnMut(&S) -> bool,
+{
+    let mut r = Search::new();
+    let mut queue = VecDeque::new();
+    for s in starts {
+        if r.dist.insert(s.clone(), 0).is_none() {
+            queue.push_back((s, 0));
+        }
+    }
+    while let Some((s, d)) = queue.pop_front() {
+        if r.goal.as_ref().is_some_and(|g| d > r.dist[g]) {
+            break;
+        }
+        if r.goal.is_none() && goal(&s) {
+            r.goal = Some(s.clone());
+        }
+        for t in next(&s) {
+            if r.relax(&t, &s, d + 1) {
+                queue.push_back((t, d + 1));
+            }
+        }
+    }
+    r
+}
+
+// Dijkstra's search, next giving the states reachable from a state
+// with the cost of each move. Stops like bfs.
+pub fn dijkstra<S, N, I, G>(starts: impl IntoIterator<Item = S>, next: N, goal: G) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+    G: FnMut(&S) -> bool,
+{
+    astar(starts, next, |_| 0, goal)
+}
+
+// A* search, guided by an estimate of the remaining cost to a goal.
+// The estimate must never be more than the cost of any move plus the estimate
+// after it, as with the manhattan distance on a grid, for the distances to be
+// the shortest ones.
+pub fn astar<S, N, I, H, G>(
+    starts: impl IntoIterator<Item = S>,
+    mut next: N,
+    mut estimate: H,
+    mut goal: G,
+) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+    H: FnMut(&S) -> usize,
+    G: FnMut(&S) -> bool,
+{
+    let mut r = Search::new();
+    // The heap refers to states by their index in states, so that they need not be Ord.
+    let mut states = vec![];
+    let mut heap = BinaryHeap::new();
+    for s in starts {
+        if r.dist.insert(s.clone(), 0).is_none() {
+            heap.push(Reverse((estimate(&s), 0, states.len())));
+            states.push(s);
+        }
+    }
+    while let Some(Reverse((f, d, i))) = heap.pop() {
+        if r.goal.as_ref().is_some_and(|g| f > r.dist[g]) {
+            break;
+        }
+        let s = states[i].clone();
+        if d > r.dist[&s] {
+            // Reached again at a shorter distance since this was queued.
+            continue;
+        }
+        if r.goal.is_none() && goal(&s) {
+            r.goal = Some(s.clone());
+        }
+        for (t, c) in next(&s) {
+            if r.relax(&t, &s, d + c) {
+                heap.push(Reverse((d + c + estimate(&t), d + c, states.len())));
+                states.push(t);
+            }
+        }
+    }
+    r
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::grid::{pt, Dir, DIRS};
+
+    #[test]
+    fn it_works() {
+        let g: Grid<char> = "\
+.....
+.#.#.
+...#.
+.#..."
+            .parse()
+            .unwrap();
+        let (start, end) = (pt(0, 0), pt(4, 3));
+        let open = |p: &Point| g.get(*p) == Some(&'.');
+        let next = |p: &Point| g.neighbors4(*p).filter(open).collect::<Vec<_>>();
+
+        let r = bfs([start], next, |p| *p == end);
+        assert_eq!(r.goal(), Some(&end));
+        assert_eq!(r.dist(&end), Some(7));
+        let path = r.path_to(&end).unwrap();
+        assert_eq!(path.len(), 8);
+        assert_eq!((path[0], path[7]), (start, end));
+        // Along the top, or down column 2 reached from above or from the left.
+        assert_eq!(r.on_paths_to([&end]).len(), 15);
+        assert_eq!(r.count_paths(&end), 3);
+
+        let all = bfs([start], next, |_| false);
+        assert_eq!(all.distances().len(), 16);
+        let dg = all.distance_grid(g.dimensions(), |p| *p);
+        assert_eq!(dg.get(pt(1, 1)), Some(&None));
+        assert_eq!(dg.get(pt(4, 0)), Some(&Some(4)));
+
+        let moves = |&(p, d): &(Point, Dir)| {
+            let mut v = vec![((p, d.left()), 10), ((p, d.right()), 10)];
+            let q = p + d.step(1);
+            if open(&q) {
+                v.push(((q, d), 1));
+            }
+            v
+        };
+        let at_end = |&(p, _): &(Point, Dir)| p == end;
+        let r = dijkstra([(start, Dir::East)], moves, at_end);
+        assert_eq!(r.dist(r.goal().unwrap()), Some(7 + 10));
+        let ends: Vec<_> = DIRS.iter().map(|&d| (end, d)).collect();
+        let tiles: HashSet<_> = r.on_paths_to(&ends).iter().map(|s| s.0).collect();
+        assert_eq!(tiles.len(), 8);
+
+        let manhattan = |&(p, _): &(Point, Dir)| (end.x - p.x + end.y - p.y) as usize;
+        let a = astar([(start, Dir::East)], moves, manhattan, at_end);
+        assert_eq!(a.dist(a.goal().unwrap()), Some(17));
+        assert!(a.distances().len() <= r.distances().len());
+    }
+}
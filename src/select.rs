@@ -0,0 +1,198 @@
+use crate::history::History;
+use anyhow::{anyhow, bail, Result};
+use aoc24::input::InputSource;
+use aoc24::{fmt_duration, year_day_funcs, DayFunc};
+use clap::Args;
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+use std::time::Duration;
+
+// Time in milliseconds separating slow days from fast ones,
+// when --slow or --fast is given without a value.
+const SLOW_MS: &str = "100";
+
+#[derive(Debug, Args)]
+pub struct DaySelection {
+    #[arg(short, long)]
+    all: bool,
+
+    /// Only days whose last recorded run took longer than this many milliseconds, as in `--slow=200`
+    #[arg(long, value_name = "MS", num_args = 0..=1, require_equals = true,
+          default_missing_value = SLOW_MS)]
+    slow: Option<f64>,
+
+    /// Only days whose last recorded run took at most this many milliseconds, as in `--fast=200`
+    #[arg(long, value_name = "MS", num_args = 0..=1, require_equals = true,
+          default_missing_value = SLOW_MS, conflicts_with = "slow")]
+    fast: Option<f64>,
+
+    /// Days to run, as numbers, ranges and lists such as `1-5,9,20-`
+    #[arg(value_name = "DAYS")]
+    days: Vec<DayList>,
+}
+
+// Days given on the command line, such as `1-5,9,20-`.
+// A range without an end goes up to the last registered day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayList(Vec<(usize, Option<usize>)>);
+
+impl DayList {
+    // Expands the list given the number of registered days.
+    // Days outside of 1..=ndays are an error.
+    fn expand(&self, ndays: usize, year: u32) -> Result<Vec<usize>> {
+        let mut v = vec![];
+        for &(start, end) in &self.0 {
+            let end = end.unwrap_or(ndays);
+            for d in [start, end] {
+                if d > ndays {
+                    bail!("day {} is not registered for {}", d, year);
+                }
+            }
+            v.extend(start..=end);
+        }
+        Ok(v)
+    }
+
+    pub fn contains(&self, day: usize) -> bool {
+        self.0
+            .iter()
+            .any(|&(start, end)| start <= day && end.is_none_or(|end| day <= end))
+    }
+}
+
+impl FromStr for DayList {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let day = |s: &str| -> Result<usize> {
+            match s.trim().parse() {
+                Ok(0) | Err(_) => Err(anyhow!("invalid day {:?}", s)),
+                Ok(n) => Ok(n),
+            }
+        };
+        let mut v = vec![];
+        for item in s.split(',') {
+            let range = match item.split_once('-') {
+                None => day(item).map(|d| (d, Some(d)))?,
+                Some((a, "")) => (day(a)?, None),
+                Some((a, b)) => (day(a)?, Some(day(b)?)),
+            };
+            if let (start, Some(end)) = range {
+                if start > end {
+                    bail!("empty range {:?}", item);
+                }
+            }
+            v.push(range);
+        }
+        Ok(DayList(v))
+    }
+}
+
+impl DaySelection {
+    // Days to filter on their recorded times.
+    fn speed(&self) -> Option<(bool, Duration)> {
+        let ms = |ms: f64| Duration::from_secs_f64(ms / 1000.0);
+        match (self.slow, self.fast) {
+            (Some(t), _) => Some((true, ms(t))),
+            (_, Some(t)) => Some((false, ms(t))),
+            _ => None,
+        }
+    }
+}
+
+pub fn get_day_funcs(is: &InputSource, cli: &DaySelection) -> Result<Vec<(usize, DayFunc)>> {
+    let year = is.year();
+    let fs = year_day_funcs(year)?;
+    let speed = cli.speed();
+
+    let days: BTreeSet<usize> = if !cli.days.is_empty() {
+        listed_days(cli, fs.len(), year)?
+    } else if cli.all || speed.is_some() {
+        (1..=fs.len()).collect()
+    } else {
+        BTreeSet::from([fs.len()])
+    };
+
+    let days = match speed {
+        Some((slow, t)) => {
+            let times = latest_times(is)?;
+            let days: BTreeSet<usize> = days
+                .into_iter()
+                .filter(|d| times.get(d).is_some_and(|&dt| (dt > t) == slow))
+                .collect();
+            if days.is_empty() {
+                let what = if slow { "longer than" } else { "at most" };
+                bail!("no recorded run took {} {}", what, fmt_duration(t));
+            }
+            days
+        }
+        None => days,
+    };
+    Ok(days.into_iter().map(|d| (d, fs[d - 1])).collect())
+}
+
+// Days listed on the command line, without a default or filtering on
+// the recorded times, so that no input source is needed.
+pub fn listed_day_funcs(year: u32, cli: &DaySelection) -> Result<Vec<(usize, DayFunc)>> {
+    let fs = year_day_funcs(year)?;
+    let days = listed_days(cli, fs.len(), year)?;
+    Ok(days.into_iter().map(|d| (d, fs[d - 1])).collect())
+}
+
+fn listed_days(cli: &DaySelection, ndays: usize, year: u32) -> Result<BTreeSet<usize>> {
+    let mut s = BTreeSet::new();
+    for list in &cli.days {
+        s.extend(list.expand(ndays, year)?);
+    }
+    Ok(s)
+}
+
+// Time of the latest recorded run of each day of the year.
+fn latest_times(is: &InputSource) -> Result<BTreeMap<usize, Duration>> {
+    let entries = History::open(is)?.load()?;
+    Ok(entries
+        .iter()
+        .filter(|e| e.year == is.year())
+        .map(|e| (e.day, e.total()))
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn it_works() {
+        let l: DayList = "1-3,9,20-".parse().unwrap();
+        assert_eq!(l.0, [(1, Some(3)), (9, Some(9)), (20, None)]);
+        assert_eq!(l.expand(22, 2024).unwrap(), [1, 2, 3, 9, 20, 21, 22]);
+        assert!(l.expand(19, 2024).is_err());
+        assert!(l.contains(2) && l.contains(30) && !l.contains(4));
+        assert!("26".parse::<DayList>().unwrap().expand(25, 2024).is_err());
+
+        for bad in ["", "0", "x", "5-3", "1,,2", "-4"] {
+            assert!(bad.parse::<DayList>().is_err(), "{bad:?}");
+        }
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            sel: DaySelection,
+        }
+        let sel = |args: &[&str]| Cli::try_parse_from(["aoc24"].iter().chain(args)).map(|c| c.sel);
+        let s = sel(&["--slow", "1-3"]).unwrap();
+        assert_eq!((s.slow, s.days.len()), (Some(100.0), 1));
+        let s = sel(&["--fast=20", "4"]).unwrap();
+        assert_eq!(s.speed(), Some((false, Duration::from_millis(20))));
+        assert!(sel(&["--slow", "--fast"]).is_err());
+
+        // Without days there is nothing listed, rather than the last day.
+        let days = |args: &[&str]| listed_day_funcs(2024, &sel(args).unwrap()).unwrap();
+        assert!(days(&[]).is_empty());
+        assert_eq!(
+            days(&["3"]).iter().map(|&(d, _)| d).collect::<Vec<_>>(),
+            [3]
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use crate::answer::{Answer, Part};
+use crate::context::Context;
+use anyhow::{anyhow, bail, Result};
+use std::fmt::Display;
+use std::time::Instant;
+
+// Parts of a day to solve.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Parts {
+    #[default]
+    Both,
+    Only(usize),
+}
+
+impl Parts {
+    // Reports whether part n is selected.
+    pub fn contains(self, n: usize) -> bool {
+        match self {
+            Parts::Both => true,
+            Parts::Only(m) => m == n,
+        }
+    }
+}
+
+// Solution of a day, split into phases that are timed separately.
+pub trait Solution {
+    // Number of parts of the puzzle.
+    const PARTS: usize = 2;
+
+    // The input, as parsed for the parts.
+    type Input<'a>;
+
+    fn parse<'a>(input: &'a str, ctx: &Context) -> Result<Self::Input<'a>>;
+
+    fn part1(input: &Self::Input<'_>, ctx: &Context) -> Result<impl Display>;
+
+    fn part2(_input: &Self::Input<'_>, _ctx: &Context) -> Result<impl Display> {
+        Err::<&str, _>(anyhow!("there is no part 2"))
+    }
+
+    // Solves both parts.
+    // Days where the parts share most of the work override this to do it once.
+    fn both(input: &Self::Input<'_>, ctx: &Context) -> Answer {
+        let part1 = Part::try_eval(|| Self::part1(input, ctx));
+        if Self::PARTS == 1 {
+            return Answer::single(part1);
+        }
+        Answer::new(part1, Part::try_eval(|| Self::part2(input, ctx)))
+    }
+}
+
+// Parses input, and solves the parts of it selected by ctx with S.
+pub fn solve<S: Solution>(input: &str, ctx: &Context) -> Result<Answer> {
+    let now = Instant::now();
+    let p = S::parse(input, ctx)?;
+    let parse = now.elapsed();
+
+    let mut answer = match ctx.parts {
+        Parts::Both => S::both(&p, ctx),
+        Parts::Only(1) => Answer::single(Part::try_eval(|| S::part1(&p, ctx))),
+        Parts::Only(2) if S::PARTS >= 2 => Answer {
+            part1: None,
+            part2: Some(Part::try_eval(|| S::part2(&p, ctx))),
+            parse,
+            alloc: None,
+        },
+        Parts::Only(n) => bail!("there is no part {}", n),
+    };
+    answer.parse = parse;
+    Ok(answer)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        type Input<'a> = Vec<&'a str>;
+
+        fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+            Ok(input.lines().collect())
+        }
+
+        fn part1(input: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+            Ok(input.len())
+        }
+
+        fn part2(input: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+            input.last().ok_or_else(|| anyhow!("no lines"))
+        }
+    }
+
+    struct Single;
+
+    impl Solution for Single {
+        const PARTS: usize = 1;
+        type Input<'a> = &'a str;
+
+        fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+            Ok(input)
+        }
+
+        fn part1(input: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+            Ok(input.len())
+        }
+    }
+
+    #[test]
+    fn it_works() {
+        let only = |n| Context::default().with_parts(Parts::Only(n));
+        let a = solve::<Lines>("a\nb\nc", &Context::default()).unwrap();
+        let v: Vec<_> = a.parts().map(|(n, p)| (n, p.value())).collect();
+        assert_eq!(v, [(1, Some("3")), (2, Some("c"))]);
+
+        let a = solve::<Lines>("a\nb", &only(2)).unwrap();
+        assert!(a.part1.is_none());
+        assert_eq!(a.part2.unwrap().value(), Some("b"));
+
+        let a = solve::<Lines>("", &only(2)).unwrap();
+        assert!(a.part2.unwrap().result.is_err());
+
+        let a = solve::<Single>("abc", &Context::default()).unwrap();
+        assert_eq!(a.parts().count(), 1);
+        assert!(solve::<Single>("abc", &only(2)).is_err());
+        assert!(solve::<Lines>("abc", &only(3)).is_err());
+    }
+}
@@ -0,0 +1,257 @@
+use anyhow::{anyhow, bail, Context, Result};
+use aoc24::input::InputSource;
+use aoc24::logger::Logger;
+use aoc24::{day_func, Parts};
+use clap::Args;
+use regex::Regex;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Args)]
+pub struct SubmitArgs {
+    day: usize,
+
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Answer to submit; the solver is run to get it if omitted
+    answer: Option<String>,
+
+    /// Submit even if the same answer was submitted before
+    #[arg(short, long)]
+    force: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited(Duration),
+    AlreadySolved,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::RateLimited(d) => write!(f, "rate limited, wait {}s", d.as_secs()),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+pub fn main(is: &InputSource, args: &SubmitArgs, logger: &Arc<Logger>) -> Result<()> {
+    let (day, part) = (args.day, args.part as usize);
+    let answer = match &args.answer {
+        Some(a) => a.trim().to_owned(),
+        None => solve(is, day, part, logger)?,
+    };
+
+    let log = Log {
+        path: is
+            .submissions_path()
+            .ok_or_else(|| anyhow!("no cache directory"))?,
+    };
+    if !args.force {
+        if let Some(prev) = log.previous(day, part, &answer)? {
+            bail!("{answer} was already submitted for day {day} part {part}: {prev}");
+        }
+    }
+
+    let outcome = submit(is, day, part, &answer)?;
+    println!("Day {:2} part {}: {}: {}", day, part, answer, outcome);
+    log.append(day, part, &answer, outcome)
+}
+
+// Runs the solver of day, and returns the answer for part.
+fn solve(is: &InputSource, day: usize, part: usize, logger: &Arc<Logger>) -> Result<String> {
+    let f = day_func(is.year(), day)?;
+    let ctx = aoc24::Context::new(day, logger.clone()).with_parts(Parts::Only(part));
+    let answer = f(&is.get(day)?, &ctx)?;
+    let p = answer
+        .parts()
+        .find_map(|(n, p)| (n == part).then_some(p))
+        .ok_or_else(|| anyhow!("day {day} has no part {part}"))?;
+    match &p.result {
+        Ok(v) => Ok(v.clone()),
+        Err(e) => bail!("day {day} part {part}: {e}"),
+    }
+}
+
+pub fn submit(is: &InputSource, day: usize, part: usize, answer: &str) -> Result<Outcome> {
+    let body = is.post_answer(day, part, answer)?;
+    parse_response(&body)
+}
+
+fn parse_response(body: &str) -> Result<Outcome> {
+    if body.contains("That's the right answer") {
+        Ok(Outcome::Correct)
+    } else if body.contains("You gave an answer too recently") {
+        let re = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        let wait = re.captures(body).map_or(0, |c| {
+            let n = |i| c.get(i).map_or(0, |m| m.as_str().parse::<u64>().unwrap());
+            n(1) * 60 + n(2)
+        });
+        Ok(Outcome::RateLimited(Duration::from_secs(wait)))
+    } else if body.contains("That's not the right answer") {
+        if body.contains("your answer is too high") {
+            Ok(Outcome::TooHigh)
+        } else if body.contains("your answer is too low") {
+            Ok(Outcome::TooLow)
+        } else {
+            Ok(Outcome::Wrong)
+        }
+    } else if body.contains("You don't seem to be solving the right level") {
+        Ok(Outcome::AlreadySolved)
+    } else {
+        bail!("unrecognized response from the answer endpoint")
+    }
+}
+
+// Log of submitted answers.
+//
+// The format is one tab separated line per submission:
+// `<unix time> <day> <part> <answer> <outcome>`.
+struct Log {
+    path: PathBuf,
+}
+
+impl Log {
+    // Returns the outcome of an earlier submission of answer, if it was judged.
+    fn previous(&self, day: usize, part: usize, answer: &str) -> Result<Option<String>> {
+        let s = match fs::read_to_string(&self.path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("reading {:?}", self.path)),
+        };
+        let (day, part) = (day.to_string(), part.to_string());
+        Ok(s.lines().rev().find_map(|l| {
+            let v: Vec<_> = l.split('\t').collect();
+            let [_, d, p, a, outcome] = v[..] else {
+                return None;
+            };
+            (d == day && p == part && a == answer && !outcome.starts_with("rate limited"))
+                .then(|| outcome.to_owned())
+        }))
+    }
+
+    fn append(&self, day: usize, part: usize, answer: &str, outcome: Outcome) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_secs();
+        let mut f = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("opening {:?}", self.path))?;
+        writeln!(f, "{}\t{}\t{}\t{}\t{}", now, day, part, answer, outcome)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // Serves a single request with body, and returns the request received.
+    fn serve_once(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let h = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut r = BufReader::new(stream);
+            let mut req = String::new();
+            let mut len = 0;
+            loop {
+                let mut line = String::new();
+                r.read_line(&mut line).unwrap();
+                if let Some(v) = line.to_lowercase().strip_prefix("content-length:") {
+                    len = v.trim().parse().unwrap();
+                }
+                req.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut data = vec![0; len];
+            r.read_exact(&mut data).unwrap();
+            req.push_str(&String::from_utf8(data).unwrap());
+
+            let mut stream = r.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            req
+        });
+        (url, h)
+    }
+
+    #[test]
+    fn it_works() {
+        let (url, h) = serve_once(
+            "<article><p>That's not the right answer; your answer is too low. \
+             Please wait one minute before trying again.</p></article>",
+        );
+        let is = InputSource::new(2024)
+            .with_base_url(&url)
+            .with_session("s3cr3t");
+        assert_eq!(submit(&is, 7, 2, "42").unwrap(), Outcome::TooLow);
+
+        let req = h.join().unwrap();
+        assert!(req.starts_with("POST /2024/day/7/answer "));
+        assert!(req.contains("cookie: session=s3cr3t\r\n"));
+        assert!(req.ends_with("\r\n\r\nlevel=2&answer=42"));
+    }
+
+    #[test]
+    fn parse_works() {
+        let p = |s| parse_response(s).unwrap();
+        assert_eq!(
+            p("<p>That's the right answer!  You are one gold star closer.</p>"),
+            Outcome::Correct
+        );
+        assert_eq!(
+            p("<p>That's not the right answer; your answer is too high.</p>"),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            p("<p>That's not the right answer.  If you're stuck, ...</p>"),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            p(
+                "<p>You gave an answer too recently; you have to wait after submitting an answer \
+               before trying again.  You have 4m 5s left to wait.</p>"
+            ),
+            Outcome::RateLimited(Duration::from_secs(245))
+        );
+        assert_eq!(
+            p("<p>You gave an answer too recently. You have 37s left to wait.</p>"),
+            Outcome::RateLimited(Duration::from_secs(37))
+        );
+        assert_eq!(
+            p("<p>You don't seem to be solving the right level.  Did you already complete it?</p>"),
+            Outcome::AlreadySolved
+        );
+        assert!(parse_response("<html>Please log in</html>").is_err());
+    }
+}
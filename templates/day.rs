@@ -0,0 +1,37 @@
+use crate::context::Context;
+use crate::solution::Solution;
+use anyhow::Result;
+use std::fmt::Display;
+
+pub struct Day;
+
+impl Solution for Day {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse<'a>(input: &'a str, _: &Context) -> Result<Self::Input<'a>> {
+        Ok(input.lines().collect())
+    }
+
+    fn part1(lines: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(lines.len())
+    }
+
+    fn part2(lines: &Self::Input<'_>, _: &Context) -> Result<impl Display> {
+        Ok(lines.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input::example;
+
+    #[test]
+    fn it_works() {
+        let input = example(__YEAR__, __DAY__, 1).unwrap();
+        let ctx = Context::default();
+        let p = Day::parse(&input, &ctx).unwrap();
+        assert_eq!(Day::part1(&p, &ctx).unwrap().to_string(), "0");
+        assert_eq!(Day::part2(&p, &ctx).unwrap().to_string(), "0");
+    }
+}
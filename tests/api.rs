@@ -0,0 +1,32 @@
+use aoc24::{day_func, extrapolate, quadmap, year_day_funcs, Context, Dir, Grid, Parts, Point};
+
+#[test]
+fn registry_works() {
+    assert_eq!(year_day_funcs(2024).unwrap().len(), 25);
+    assert!(day_func(2024, 26).is_err());
+    assert!(year_day_funcs(1999).is_err());
+
+    let input = aoc24::input::example(2024, 9, 1).unwrap();
+    let f = day_func(2024, 9).unwrap();
+    let answer = f(input.trim(), &Context::default()).unwrap();
+    assert_eq!(answer.part1.unwrap().value(), Some("1928"));
+    assert_eq!(answer.part2.unwrap().value(), Some("2858"));
+
+    let answer = f(input.trim(), &Context::default().with_parts(Parts::Only(2))).unwrap();
+    assert!(answer.part1.is_none());
+    assert_eq!(answer.part2.unwrap().value(), Some("2858"));
+}
+
+#[test]
+fn grid_works() {
+    let g = Grid::parse("ab\ncd\n").unwrap();
+    assert_eq!(g.dimensions(), (2, 2));
+    let p = Point::new(0, 0) + Dir::East.step(1);
+    assert_eq!(g.get(p), Some(&b'b'));
+
+    let mut m = quadmap::Map::new(0u8);
+    *m.at_mut((-3, 5)) = 1;
+    assert_eq!(*m.at((-3, 5)), 1);
+
+    assert_eq!(extrapolate::nth((0..).map(|n| n * n), 100), Some(100 * 100));
+}